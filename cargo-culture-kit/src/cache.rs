@@ -0,0 +1,446 @@
+//! Reuse of earlier `Rule` outcomes while the inputs the `Rule`s declare
+//! are unchanged.
+//!
+//! The outcomes are kept in a JSON file within the directory the `Rule`s
+//! build the project in, keyed by `Rule` id, alongside a fingerprint of the
+//! checked manifest, the `Rule` and its configuration, the toolchain, and
+//! the content of its inputs. The fingerprint is a SHA-256 digest, so that
+//! it stays comparable across builds of this crate and releases of Rust.
+use super::process;
+use super::report::RuleReport;
+use super::rules::{culture_target_dir, Finding, Rule, RuleOutcome};
+use super::CargoMetadata;
+use serde_json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// The name of the file, within the culture target directory, in which
+/// `Rule` outcomes are cached
+pub const CULTURE_CACHE_FILE_NAME: &str = "results.json";
+
+/// The remembered evaluation of a single `Rule`
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct CachedEvaluation {
+    fingerprint: String,
+    outcome: RuleOutcome,
+    details: String,
+    findings: Vec<Finding>,
+}
+
+/// The cached outcomes of a project's `Rule`s, as of the start of a check,
+/// along with any recorded since.
+#[derive(Debug)]
+pub(crate) struct ResultCache {
+    path: PathBuf,
+    /// The canonical path of the manifest being checked, since the same
+    /// workspace may be checked through the manifest of any of its members
+    manifest_path: PathBuf,
+    workspace_root: PathBuf,
+    /// The workspace root and the directories of the packages within it,
+    /// against which the `inputs` of `Rule`s are resolved
+    package_dirs: Vec<PathBuf>,
+    target_dir: PathBuf,
+    /// The version descriptions of `cargo` and `rustc`, whose change may
+    /// change the outcome of any `Rule`, found when first needed. `None`
+    /// when they could not be found, in which case nothing is cached.
+    toolchain: OnceLock<Option<Vec<u8>>>,
+    evaluations: Mutex<HashMap<String, CachedEvaluation>>,
+}
+
+impl ResultCache {
+    /// Read the cache of the project described by `metadata`, starting
+    /// afresh if there is none or it cannot be read. Without `metadata`,
+    /// there is no telling where the cache belongs, so there is none.
+    pub(crate) fn load(
+        cargo_manifest_file_path: &Path,
+        metadata: &Option<CargoMetadata>,
+    ) -> Option<Self> {
+        let m = metadata.as_ref()?;
+        let path =
+            culture_target_dir(cargo_manifest_file_path, metadata).join(CULTURE_CACHE_FILE_NAME);
+        let evaluations = File::open(&path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(file).ok())
+            .unwrap_or_default();
+        let workspace_root = PathBuf::from(&m.workspace_root);
+        let mut package_dirs: Vec<PathBuf> = m
+            .packages
+            .iter()
+            .filter_map(|p| Path::new(&p.manifest_path).parent().map(Path::to_path_buf))
+            .filter(|dir| dir.starts_with(&workspace_root))
+            .chain(Some(workspace_root.clone()))
+            .collect();
+        package_dirs.sort();
+        package_dirs.dedup();
+        Some(ResultCache {
+            path,
+            manifest_path: cargo_manifest_file_path
+                .canonicalize()
+                .unwrap_or_else(|_| cargo_manifest_file_path.to_path_buf()),
+            workspace_root,
+            package_dirs,
+            target_dir: PathBuf::from(&m.target_directory),
+            toolchain: OnceLock::new(),
+            evaluations: Mutex::new(evaluations),
+        })
+    }
+
+    /// A digest of the checked manifest, the `rule` and its configuration,
+    /// the toolchain, and the current content of its `inputs`, or `None` for
+    /// a `Rule` that declares no inputs, or when the toolchain in use cannot
+    /// be found out by the `deadline`.
+    pub(crate) fn fingerprint(
+        &self,
+        rule: &Rule,
+        verbose: bool,
+        deadline: Option<Instant>,
+    ) -> Option<String> {
+        let inputs = rule.inputs();
+        if inputs.is_empty() {
+            return None;
+        }
+        let toolchain = self
+            .toolchain
+            .get_or_init(|| toolchain_versions(&self.manifest_path, deadline))
+            .as_ref()?;
+        let mut hasher = Sha256::new();
+        hash_field(&mut hasher, env!("CARGO_PKG_VERSION").as_bytes());
+        hash_field(&mut hasher, toolchain);
+        hash_field(&mut hasher, self.manifest_path.to_string_lossy().as_bytes());
+        hash_field(&mut hasher, rule.id().as_bytes());
+        hash_field(&mut hasher, rule.configuration().as_bytes());
+        hash_field(&mut hasher, &[verbose as u8]);
+        for input in inputs {
+            hash_field(&mut hasher, input.to_string_lossy().as_bytes());
+            for dir in &self.package_dirs {
+                self.hash_content(&dir.join(&input), &mut hasher);
+            }
+        }
+        Some(
+            hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        )
+    }
+
+    fn hash_content(&self, path: &Path, hasher: &mut Sha256) {
+        let is_symlink = fs::symlink_metadata(path)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false);
+        if path.is_dir() && is_symlink {
+            // A linked directory may lead back up the tree, or out of the
+            // project altogether, so only where it leads is considered
+            hasher.update(b"l");
+            let target = fs::read_link(path).unwrap_or_default();
+            hash_field(hasher, target.to_string_lossy().as_bytes());
+        } else if path.is_dir() {
+            let mut entries: Vec<PathBuf> = match fs::read_dir(path) {
+                Ok(entries) => entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| !self.is_excluded(p))
+                    .collect(),
+                Err(_) => return,
+            };
+            entries.sort();
+            hasher.update(b"d");
+            for entry in entries {
+                let name = entry
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                hash_field(hasher, name.as_bytes());
+                self.hash_content(&entry, hasher);
+            }
+        } else if let Ok(content) = fs::read(path) {
+            hasher.update(b"f");
+            hash_field(hasher, &content);
+        } else {
+            // Distinguish an absent input from an empty one
+            hasher.update(b"-");
+        }
+    }
+
+    /// Whether `dir` holds build output or version control state, rather
+    /// than anything that `Rule`s inspect
+    fn is_excluded(&self, dir: &Path) -> bool {
+        dir == self.target_dir || dir == self.workspace_root.join(".git")
+    }
+
+    /// The earlier report of the `rule`, if it was evaluated when its
+    /// inputs had the same `fingerprint`.
+    pub(crate) fn lookup(
+        &self,
+        rule: &Rule,
+        fingerprint: &str,
+        duration: Duration,
+    ) -> Option<RuleReport> {
+        let evaluations = self.evaluations.lock().expect("Result cache poisoned");
+        let evaluation = evaluations.get(rule.id())?;
+        if evaluation.fingerprint != fingerprint {
+            return None;
+        }
+        Some(RuleReport {
+            id: rule.id().to_owned(),
+            description: rule.description().to_owned(),
+            severity: rule.severity(),
+            outcome: evaluation.outcome.clone(),
+            duration,
+            details: evaluation.details.clone(),
+            findings: evaluation.findings.clone(),
+            cached: true,
+        })
+    }
+
+    /// Remember the `report` of a `Rule` evaluated when its inputs had the
+    /// supplied `fingerprint`. Only conclusive outcomes are remembered, so
+    /// that, for example, a `Rule` that ran out of time is tried again.
+    pub(crate) fn record(&self, fingerprint: String, report: &RuleReport) {
+        let mut evaluations = self.evaluations.lock().expect("Result cache poisoned");
+        match report.outcome {
+            RuleOutcome::Success | RuleOutcome::Failure => {
+                evaluations.insert(
+                    report.id.clone(),
+                    CachedEvaluation {
+                        fingerprint,
+                        outcome: report.outcome.clone(),
+                        details: report.details.clone(),
+                        findings: report.findings.clone(),
+                    },
+                );
+            }
+            _ => {
+                evaluations.remove(&report.id);
+            }
+        }
+    }
+
+    /// Write the cache back to disk, for the next check to use.
+    pub(crate) fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let evaluations = self.evaluations.lock().expect("Result cache poisoned");
+        serde_json::to_writer(File::create(&self.path)?, &*evaluations).map_err(io::Error::other)
+    }
+}
+
+/// Add `bytes` to the digest, prefixed with their length so that adjacent
+/// fields cannot run into one another
+fn hash_field(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// The verbose version descriptions of the `cargo` and `rustc` that build
+/// the project of the manifest at `manifest_path`, as chosen by any
+/// toolchain override of that project, or `None` if either of them cannot
+/// be run to completion by the `deadline`
+fn toolchain_versions(manifest_path: &Path, deadline: Option<Instant>) -> Option<Vec<u8>> {
+    let project_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut versions = Vec::new();
+    for program in &[get_cargo_command(), get_rustc_command()] {
+        let mut command = Command::new(program);
+        command.arg("-vV").current_dir(project_dir);
+        let output = process::run(&mut command, None, deadline).ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        versions.extend(output.stdout);
+        versions.push(0);
+    }
+    Some(versions)
+}
+
+fn get_cargo_command() -> String {
+    ::std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"))
+}
+
+fn get_rustc_command() -> String {
+    ::std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::reporter::test_support::rule_report;
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[derive(Debug)]
+    struct ManifestRule;
+
+    impl Rule for ManifestRule {
+        fn description(&self) -> &str {
+            "Should have a manifest."
+        }
+
+        fn inputs(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from(".")]
+        }
+
+        fn evaluate(&self, _context: ::RuleContext) -> RuleOutcome {
+            RuleOutcome::Success
+        }
+    }
+
+    #[derive(Debug)]
+    struct ConfiguredRule(&'static str);
+
+    impl Rule for ConfiguredRule {
+        fn description(&self) -> &str {
+            "Should be configured."
+        }
+
+        fn inputs(&self) -> Vec<PathBuf> {
+            vec![PathBuf::from("Cargo.toml")]
+        }
+
+        fn configuration(&self) -> String {
+            self.0.to_owned()
+        }
+
+        fn evaluate(&self, _context: ::RuleContext) -> RuleOutcome {
+            RuleOutcome::Success
+        }
+    }
+
+    fn cache_at(root: &Path) -> ResultCache {
+        ResultCache {
+            path: root.join("target").join(CULTURE_CACHE_FILE_NAME),
+            manifest_path: root.join("Cargo.toml"),
+            workspace_root: root.to_path_buf(),
+            package_dirs: vec![root.to_path_buf()],
+            target_dir: root.join("target"),
+            toolchain: OnceLock::from(Some(b"rustc 1.0.0".to_vec())),
+            evaluations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn report(outcome: RuleOutcome) -> RuleReport {
+        RuleReport {
+            duration: Duration::from_secs(5),
+            ..rule_report(&ManifestRule, outcome)
+        }
+    }
+
+    #[test]
+    fn fingerprint_follows_the_content_of_inputs_but_not_target_dirs() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let cache = cache_at(dir.path());
+        let mut manifest = File::create(dir.path().join("Cargo.toml")).expect("Should create");
+        let before = cache.fingerprint(&ManifestRule, false, None);
+        assert!(before.is_some());
+        fs::create_dir_all(dir.path().join("target")).expect("Should create");
+        File::create(dir.path().join("target").join("artifact")).expect("Should create");
+        assert_eq!(before, cache.fingerprint(&ManifestRule, false, None));
+        assert_ne!(before, cache.fingerprint(&ManifestRule, true, None));
+        manifest.write_all(b"[package]").expect("Should write");
+        assert_ne!(before, cache.fingerprint(&ManifestRule, false, None));
+    }
+
+    #[test]
+    fn fingerprint_follows_source_dirs_named_target() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let cache = cache_at(dir.path());
+        let nested = dir.path().join("src").join("target");
+        fs::create_dir_all(&nested).expect("Should create");
+        let before = cache.fingerprint(&ManifestRule, false, None);
+        File::create(nested.join("mod.rs")).expect("Should create");
+        assert_ne!(before, cache.fingerprint(&ManifestRule, false, None));
+    }
+
+    #[test]
+    fn fingerprint_follows_the_toolchain() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let cache = cache_at(dir.path());
+        let upgraded = ResultCache {
+            toolchain: OnceLock::from(Some(b"rustc 2.0.0".to_vec())),
+            ..cache_at(dir.path())
+        };
+        assert_ne!(
+            cache.fingerprint(&ManifestRule, false, None),
+            upgraded.fingerprint(&ManifestRule, false, None)
+        );
+    }
+
+    #[test]
+    fn fingerprint_follows_the_checked_manifest_and_configuration() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let cache = cache_at(dir.path());
+        let member = ResultCache {
+            manifest_path: dir.path().join("member").join("Cargo.toml"),
+            ..cache_at(dir.path())
+        };
+        let rule = ConfiguredRule("{\"minimum\":2}");
+        let before = cache.fingerprint(&rule, false, None);
+        assert_ne!(before, member.fingerprint(&rule, false, None));
+        assert_ne!(
+            before,
+            cache.fingerprint(&ConfiguredRule("{\"minimum\":3}"), false, None)
+        );
+    }
+
+    #[test]
+    fn fingerprint_follows_the_inputs_of_every_package() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let member = dir.path().join("member");
+        fs::create_dir_all(&member).expect("Should create");
+        let cache = ResultCache {
+            package_dirs: vec![dir.path().to_path_buf(), member.clone()],
+            ..cache_at(dir.path())
+        };
+        let rule = ConfiguredRule("");
+        let before = cache.fingerprint(&rule, false, None);
+        File::create(member.join("Cargo.toml")).expect("Should create");
+        assert_ne!(before, cache.fingerprint(&rule, false, None));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fingerprint_does_not_follow_linked_dirs() {
+        use std::os::unix::fs::symlink;
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let cache = cache_at(dir.path());
+        let outside = tempdir().expect("Failed to make a temp dir");
+        symlink(dir.path(), dir.path().join("loop")).expect("Should link");
+        symlink(outside.path(), dir.path().join("outside")).expect("Should link");
+        let before = cache.fingerprint(&ManifestRule, false, None);
+        assert!(before.is_some());
+        File::create(outside.path().join("elsewhere")).expect("Should create");
+        assert_eq!(before, cache.fingerprint(&ManifestRule, false, None));
+    }
+
+    #[test]
+    fn conclusive_outcomes_are_remembered_across_loads() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let cache = cache_at(dir.path());
+        cache.record("abc".to_owned(), &report(RuleOutcome::Failure));
+        cache.save().expect("Should save");
+        let reloaded = ResultCache {
+            evaluations: Mutex::new(
+                serde_json::from_reader(File::open(&cache.path).expect("Should open"))
+                    .expect("Should parse"),
+            ),
+            ..cache_at(dir.path())
+        };
+        let cached = reloaded
+            .lookup(&ManifestRule, "abc", Duration::from_secs(0))
+            .expect("Should be cached");
+        assert!(cached.cached);
+        assert_eq!(RuleOutcome::Failure, cached.outcome);
+        assert_eq!(None, reloaded.lookup(&ManifestRule, "def", Duration::from_secs(0)));
+
+        reloaded.record("abc".to_owned(), &report(RuleOutcome::Undetermined));
+        assert_eq!(None, reloaded.lookup(&ManifestRule, "abc", Duration::from_secs(0)));
+    }
+}
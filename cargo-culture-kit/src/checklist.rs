@@ -1,8 +1,8 @@
 //! Helper functions related to the interpretation and filtering of `Rule`
-//! description checklists.
+//! checklists.
 //!
-//! These checklists can be encoded as a line-delimited file of `Rule`
-//! descriptions.
+//! These checklists can be encoded as a line-delimited file of `Rule` ids or
+//! descriptions. Blank lines and lines starting with `#` are ignored.
 use super::Rule;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -36,6 +36,18 @@ pub enum FilterError {
         /// found.
         rule_description: String,
     },
+    #[fail(
+        display = "The requested rule \"{}\" matches more than one of the available rules: {:?}",
+        request, candidates
+    )]
+    /// A request for a single `Rule` matched the `description`s of several of
+    /// the available `Rule` instances.
+    AmbiguousRuleRequest {
+        /// The problematic request
+        request: String,
+        /// The `description`s of all of the `Rule`s which matched the request
+        candidates: Vec<String>,
+    },
     /// Destructuring should not be exhaustive.
     ///
     /// This enum may grow additional variants, so this hidden variant
@@ -51,6 +63,15 @@ pub enum FilterError {
 /// Otherwise, search the specified path and its ancestor directories for a file
 /// with a name matching the `DEFAULT_CULTURE_CHECKLIST_FILE_NAME`
 pub fn find_extant_culture_file(initial_culture_file: &Path) -> Option<PathBuf> {
+    find_extant_file_named(initial_culture_file, DEFAULT_CULTURE_CHECKLIST_FILE_NAME)
+}
+
+/// Return `initial_culture_file` if it is an extant file, or else the first
+/// file named `file_name` in its directory or that directory's ancestors.
+pub(crate) fn find_extant_file_named(
+    initial_culture_file: &Path,
+    file_name: &str,
+) -> Option<PathBuf> {
     let first_dir = if initial_culture_file.is_file() {
         return Some(PathBuf::from(initial_culture_file));
     } else if initial_culture_file.is_dir() {
@@ -62,7 +83,7 @@ pub fn find_extant_culture_file(initial_culture_file: &Path) -> Option<PathBuf>
     loop {
         p = match p {
             Some(dir) => {
-                let potential_culture_file = dir.join(DEFAULT_CULTURE_CHECKLIST_FILE_NAME);
+                let potential_culture_file = dir.join(file_name);
                 if potential_culture_file.is_file() {
                     return Some(potential_culture_file);
                 } else {
@@ -75,9 +96,12 @@ pub fn find_extant_culture_file(initial_culture_file: &Path) -> Option<PathBuf>
 }
 
 /// Produces a filtered subset of the provided `Rule`s by
-/// matching their `description`s to the lines of the
+/// matching their `id`s or `description`s to the lines of the
 /// the file specified by `culture_checklist_file_path`.
 ///
+/// Surrounding whitespace is trimmed from each line, and blank lines
+/// as well as comment lines starting with `#` are skipped.
+///
 /// # Errors
 ///
 /// Returns a `FilterError::RuleChecklistReadError` error when one of the lines
@@ -99,7 +123,9 @@ pub fn filter_to_requested_rules_from_checklist_file<'path, 'rules>(
     let mut descriptions: Vec<String> = Vec::new();
     for line in content.lines() {
         match line {
-            Ok(ref l) if !l.is_empty() => descriptions.push(l.to_string()),
+            Ok(ref l) if !is_checklist_comment_or_blank(l) => {
+                descriptions.push(l.trim().to_string())
+            }
             Ok(_) => (),
             Err(_) => {
                 return Err(FilterError::RuleChecklistReadError(format!(
@@ -117,13 +143,14 @@ pub fn filter_to_requested_rules_from_checklist_file<'path, 'rules>(
 }
 
 /// Produces a filtered subset of the provided `Rule`s by
-/// matching their `description`s to the members of the
+/// matching their `id`s or `description`s to the members of the
 /// the `desired_rule_descriptions` slice.
 ///
 /// # Errors
 ///
-/// Returns a `FilterError::RuleChecklistReadError` error when one of the lines
-/// of the file does not match any of the provided `Rule` descriptions.
+/// Returns a `FilterError::RequestedRuleNotFound` error when one of the
+/// members of `desired_rule_descriptions` does not match any of the provided
+/// `Rule` ids or descriptions.
 pub fn filter_to_requested_rules_by_description<'r, 'd>(
     available_rules: &'r [&Rule],
     desired_rule_descriptions: &'d [&str],
@@ -135,7 +162,7 @@ pub fn filter_to_requested_rules_by_description<'r, 'd>(
     for description in desired_rule_descriptions {
         match available_rules
             .iter()
-            .find(|r| &r.description() == description)
+            .find(|r| &r.id() == description || &r.description() == description)
         {
             Some(r) => rules.push(*r),
             None => {
@@ -148,6 +175,75 @@ pub fn filter_to_requested_rules_by_description<'r, 'd>(
     Ok(rules)
 }
 
+/// Find the single `Rule` among the `available_rules` that a human most
+/// likely meant by `request`.
+///
+/// A `Rule` whose `id`, ignoring case, or `description` is exactly `request`
+/// is preferred. Otherwise `request` may be any case-insensitive fragment of
+/// a `description`, provided it is found in only one.
+///
+/// # Errors
+///
+/// Returns a `FilterError::RequestedRuleNotFound` error when no `Rule`
+/// matches, or a `FilterError::AmbiguousRuleRequest` error when several do.
+pub fn find_rule<'r>(
+    available_rules: &'r [&Rule],
+    request: &str,
+) -> Result<&'r Rule, FilterError> {
+    if let Some(r) = available_rules
+        .iter()
+        .find(|r| r.id().eq_ignore_ascii_case(request.trim()) || r.description() == request)
+    {
+        return Ok(*r);
+    }
+    let lowercase_request = request.trim().to_lowercase();
+    let candidates: Vec<&Rule> = available_rules
+        .iter()
+        .cloned()
+        .filter(|r| r.description().to_lowercase().contains(&lowercase_request))
+        .collect();
+    match candidates.len() {
+        1 => Ok(candidates[0]),
+        0 => Err(FilterError::RequestedRuleNotFound {
+            rule_description: request.to_string(),
+        }),
+        _ => Err(FilterError::AmbiguousRuleRequest {
+            request: request.to_string(),
+            candidates: candidates
+                .iter()
+                .map(|r| r.description().to_string())
+                .collect(),
+        }),
+    }
+}
+
+/// Produces the content of a checklist file that requests the `selected`
+/// `Rule`s by `id`, in order, followed by the `unselected` `Rule`s as
+/// comments so that they can be readily enabled later.
+///
+/// Each `id` is preceded by a comment with the `Rule`'s `description`.
+pub fn format_checklist(selected: &[&Rule], unselected: &[&Rule]) -> String {
+    let mut content = String::from(
+        "# Rules checked by `cargo culture`, one id or description per line.\n\
+         # Lines starting with `#` are ignored.\n",
+    );
+    for rule in selected {
+        content.push_str(&format!("\n# {}\n{}\n", rule.description(), rule.id()));
+    }
+    if !unselected.is_empty() {
+        content.push_str("\n# Not yet upheld by this project:\n");
+        for rule in unselected {
+            content.push_str(&format!("\n# {}\n# {}\n", rule.description(), rule.id()));
+        }
+    }
+    content
+}
+
+fn is_checklist_comment_or_blank(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::{HasLicenseFile, HasReadmeFile};
@@ -314,4 +410,69 @@ mod tests {
             panic!("Expected an error due to a lack of a checklist file");
         }
     }
+
+    #[test]
+    fn formatted_checklist_round_trips_through_filter() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let file_path = dir.path().join(DEFAULT_CULTURE_CHECKLIST_FILE_NAME);
+        let rule_a = HasReadmeFile::default();
+        let rule_b = HasLicenseFile::default();
+        let raw_rules: &[&Rule] = &[&rule_a, &rule_b];
+
+        let content = format_checklist(&[&rule_b], &[&rule_a]);
+        assert!(content.contains(&format!("\n# {}\n", rule_a.description())));
+        let mut file = File::create(&file_path).expect("Could not make target file");
+        file.write_all(content.as_bytes())
+            .expect("Could not write to target file");
+
+        let filtered_rules = filter_to_requested_rules_from_checklist_file(&file_path, raw_rules)
+            .expect("Filtering should work when the file is present");
+        assert_eq!(
+            vec![rule_b.description()],
+            filtered_rules
+                .iter()
+                .map(|r| r.description())
+                .collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn filter_by_description_accepts_ids_and_descriptions() {
+        let rule_a = HasReadmeFile::default();
+        let rule_b = HasLicenseFile::default();
+        let raw_rules: &[&Rule] = &[&rule_a, &rule_b];
+
+        let requested = [rule_b.description(), "has-readme-file"];
+        let filtered_rules = filter_to_requested_rules_by_description(raw_rules, &requested)
+            .expect("Should find both rules");
+        assert_eq!(
+            vec![rule_b.description(), rule_a.description()],
+            filtered_rules
+                .iter()
+                .map(|r| r.description())
+                .collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn find_rule_by_exact_or_partial_description() {
+        let rule_a = HasReadmeFile::default();
+        let rule_b = HasLicenseFile::default();
+        let raw_rules: &[&Rule] = &[&rule_a, &rule_b];
+        let found = |request| find_rule(raw_rules, request).map(|r| r.description());
+
+        assert_eq!(Ok(rule_a.description()), found(rule_a.description()));
+        assert_eq!(Ok(rule_b.description()), found("license"));
+        assert_eq!(Ok(rule_a.description()), found("Has-Readme-File"));
+        match found("should have a") {
+            Err(FilterError::AmbiguousRuleRequest { candidates, .. }) => {
+                assert_eq!(2, candidates.len())
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+        match found("halting problem") {
+            Err(FilterError::RequestedRuleNotFound { .. }) => (),
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
 }
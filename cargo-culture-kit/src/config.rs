@@ -0,0 +1,900 @@
+//! Structured configuration of the `Rule`s to check, read from a TOML file.
+//!
+//! Unlike a line-delimited checklist, a configuration file can also adjust
+//! the behavior of individual `Rule`s through a table named after the
+//! `Rule`'s id. For example:
+//!
+//! ```toml
+//! rules = ["passes-multiple-tests", "has-continuous-integration-file", "builds-cleanly"]
+//! plugins = ["tools/cargo-culture-rule-codeowners"]
+//!
+//! [passes-multiple-tests]
+//! minimum-passing-tests = 10
+//!
+//! [has-continuous-integration-file]
+//! providers = ["travis", "gitlab-ci"]
+//!
+//! [builds-cleanly]
+//! allowed-lints = ["dead_code"]
+//! allowed-paths = ["src/generated/**"]
+//! maximum-warnings = 3
+//!
+//! [severities]
+//! uses-property-based-test-library = "warning"
+//!
+//! [[file-rules]]
+//! id = "has-codeowners-file"
+//! description = "Should have a CODEOWNERS file."
+//! file-name = "^CODEOWNERS$"
+//! scopes = ["workspace-root", "github"]
+//!
+//! [[dependency-rules]]
+//! id = "does-not-depend-on-openssl"
+//! description = "Should not depend on openssl."
+//! requirement = "forbidden"
+//! name = "^openssl"
+//! kinds = ["normal", "build"]
+//! ```
+//!
+//! The `rules` list accepts `Rule` ids or descriptions, just like a
+//! checklist. When it is absent, all of the `default_rules` are checked.
+//!
+//! The `severities` table overrides the `Severity` of `Rule`s by id, so that
+//! they may be merely advisory.
+//!
+//! Each of the `file-rules` declares an additional `HasMatchingFile` rule,
+//! and each of the `dependency-rules` an additional `DependencyPolicy` rule,
+//! which are checked alongside the default `Rule`s. So are the `Rule`s of
+//! the listed `plugins`, which are `ExternalRule` executables whose paths
+//! are relative to the configuration file.
+use super::checklist::{filter_to_requested_rules_by_description, find_extant_file_named};
+use super::plugin::{ExternalRule, PluginError, PLUGIN_DESCRIBE_TIMEOUT};
+use super::rules::{
+    default_rules, BuildsCleanlyWithoutWarningsOrErrors, DependencyPolicy, DependencyRequirement,
+    FileSearchScope, HasContinuousIntegrationFile, HasMatchingFile, PassesMultipleTests, RuleCost,
+    Severity,
+};
+use super::scaffold::{ProjectInfo, Scaffold};
+use super::{FilterError, Rule, RuleContext, RuleOutcome};
+use cargo_metadata::DependencyKind;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use toml;
+
+/// The default name for a culture configuration file,
+/// used when searching for a configuration file
+pub const DEFAULT_CULTURE_CONFIG_FILE_NAME: &str = ".culture.toml";
+
+/// Errors specific to reading a `CultureConfig` and applying it to `Rule`s.
+#[derive(Debug, Clone, Eq, Fail, PartialEq, Hash)]
+pub enum ConfigError {
+    #[fail(
+        display = "There was an error while attempting to read the culture configuration file {}: {}",
+        path, cause
+    )]
+    /// Failure reading the content of a configuration file.
+    ReadFailure {
+        /// The configuration file that could not be read
+        path: String,
+        /// A human-oriented description of the underlying problem
+        cause: String,
+    },
+    #[fail(display = "The culture configuration is not valid TOML of the expected shape: {}", _0)]
+    /// The configuration is not TOML, or contains unexpected keys or values.
+    ParseFailure(String),
+    #[fail(display = "The configuration of the rule {} is not valid: {}", rule_id, message)]
+    /// The options supplied for a `Rule` were well-formed, but could not be
+    /// used to configure that `Rule`.
+    InvalidRuleOption {
+        /// The `id` of the misconfigured `Rule`
+        rule_id: String,
+        /// A human-oriented description of the problem
+        message: String,
+    },
+    #[fail(display = "{}", _0)]
+    /// One of the `plugins` could not be loaded.
+    PluginFailure(PluginError),
+    /// Destructuring should not be exhaustive.
+    ///
+    /// This enum may grow additional variants, so this hidden variant
+    /// ensures users do not rely on exhaustive matching.
+    #[doc(hidden)]
+    #[fail(display = "A hidden variant to increase expansion flexibility")]
+    __Nonexhaustive,
+}
+
+/// The content of a culture configuration file: which `Rule`s to check,
+/// and how to configure them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CultureConfig {
+    /// The ids or descriptions of the `Rule`s to check, in order.
+    /// All of the default `Rule`s are checked when absent.
+    pub rules: Option<Vec<String>>,
+    /// Options for `PassesMultipleTests`
+    #[serde(rename = "passes-multiple-tests")]
+    pub passes_multiple_tests: PassesMultipleTestsConfig,
+    /// Options for `HasContinuousIntegrationFile`
+    #[serde(rename = "has-continuous-integration-file")]
+    pub has_continuous_integration_file: HasContinuousIntegrationFileConfig,
+    /// Options for `BuildsCleanlyWithoutWarningsOrErrors`
+    #[serde(rename = "builds-cleanly")]
+    pub builds_cleanly: BuildsCleanlyConfig,
+    /// The `Severity` of `Rule`s, by `id`, for those that should not have
+    /// their own default `Severity`
+    pub severities: BTreeMap<String, Severity>,
+    /// Additional `Rule`s that look for a file, declared in configuration
+    #[serde(rename = "file-rules")]
+    pub file_rules: Vec<FileRuleConfig>,
+    /// Additional `Rule`s about dependencies, declared in configuration
+    #[serde(rename = "dependency-rules")]
+    pub dependency_rules: Vec<DependencyRuleConfig>,
+    /// The paths of rule plugin executables whose `ExternalRule`s are
+    /// checked in addition to the other `Rule`s
+    pub plugins: Vec<PathBuf>,
+}
+
+/// Options for `PassesMultipleTests`
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct PassesMultipleTestsConfig {
+    /// The fewest passing tests that satisfy the `Rule`
+    pub minimum_passing_tests: Option<usize>,
+}
+
+/// Options for `HasContinuousIntegrationFile`
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct HasContinuousIntegrationFileConfig {
+    /// The continuous integration providers whose configuration files satisfy
+    /// the `Rule`, from among `HasContinuousIntegrationFile::PROVIDERS`
+    pub providers: Option<Vec<String>>,
+}
+
+/// Options for `BuildsCleanlyWithoutWarningsOrErrors`
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct BuildsCleanlyConfig {
+    /// The lints whose warnings are tolerated, such as `dead_code`
+    pub allowed_lints: Vec<String>,
+    /// Globs matching the paths, relative to the workspace root, of the
+    /// files whose warnings are tolerated, such as `src/generated/**`
+    pub allowed_paths: Vec<String>,
+    /// The most warnings that are not otherwise tolerated which still
+    /// satisfy the `Rule`
+    pub maximum_warnings: Option<usize>,
+}
+
+impl BuildsCleanlyConfig {
+    fn to_rule(&self) -> Result<BuildsCleanlyWithoutWarningsOrErrors, ConfigError> {
+        let rule = BuildsCleanlyWithoutWarningsOrErrors::default()
+            .with_allowed_lints(self.allowed_lints.clone())
+            .with_allowed_paths(&self.allowed_paths)?;
+        Ok(match self.maximum_warnings {
+            Some(maximum) => rule.with_maximum_warnings(maximum),
+            None => rule,
+        })
+    }
+}
+
+/// The declaration of a `HasMatchingFile` rule
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FileRuleConfig {
+    /// The `Rule::id`, which must not be shared with any other `Rule`
+    pub id: String,
+    /// The `Rule::description`
+    pub description: String,
+    /// A regular expression that the name of the file must match
+    pub file_name: String,
+    /// Where to look for the file, which is the package directory and the
+    /// workspace root when absent
+    #[serde(default = "default_file_rule_scopes")]
+    pub scopes: Vec<FileSearchScope>,
+    /// Whether an empty file fails the `Rule`, which it does when absent
+    #[serde(default = "default_file_rule_require_non_empty")]
+    pub require_non_empty: bool,
+    /// The `Rule::justification`, if any
+    #[serde(default)]
+    pub justification: Option<String>,
+    /// The `Rule::remediation`, if any
+    #[serde(default)]
+    pub remediation: Option<String>,
+}
+
+fn default_file_rule_scopes() -> Vec<FileSearchScope> {
+    vec![FileSearchScope::PackageDir, FileSearchScope::WorkspaceRoot]
+}
+
+fn default_file_rule_require_non_empty() -> bool {
+    true
+}
+
+impl FileRuleConfig {
+    fn to_rule(&self) -> Result<HasMatchingFile, ConfigError> {
+        let invalid = |message: String| ConfigError::InvalidRuleOption {
+            rule_id: self.id.clone(),
+            message,
+        };
+        if self.scopes.is_empty() {
+            return Err(invalid(String::from("at least one scope must be searched")));
+        }
+        let regex = Regex::new(&self.file_name)
+            .map_err(|e| invalid(format!("the file-name is not a valid regex: {}", e)))?;
+        let mut rule = HasMatchingFile::new(self.id.as_str(), self.description.as_str(), regex)
+            .with_scopes(self.scopes.clone())
+            .with_require_non_empty(self.require_non_empty);
+        if let Some(ref justification) = self.justification {
+            rule = rule.with_justification(justification.as_str());
+        }
+        if let Some(ref remediation) = self.remediation {
+            rule = rule.with_remediation(remediation.as_str());
+        }
+        Ok(rule)
+    }
+}
+
+/// The declaration of a `DependencyPolicy` rule
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DependencyRuleConfig {
+    /// The `Rule::id`, which must not be shared with any other `Rule`
+    pub id: String,
+    /// The `Rule::description`
+    pub description: String,
+    /// Whether matching dependencies must or must not be present
+    pub requirement: DependencyRequirement,
+    /// A regular expression that the names of the dependencies must match
+    pub name: String,
+    /// The kinds of dependencies to consider, from among `normal`, `dev`
+    /// and `build`, which is all of them when absent
+    #[serde(default)]
+    pub kinds: Vec<DependencyKind>,
+    /// A regular expression that the names of the inspected workspace
+    /// members must match, which is every member when absent
+    #[serde(default)]
+    pub members: Option<String>,
+    /// The `Rule::justification`, if any
+    #[serde(default)]
+    pub justification: Option<String>,
+    /// The `Rule::remediation`, if any
+    #[serde(default)]
+    pub remediation: Option<String>,
+}
+
+impl DependencyRuleConfig {
+    fn to_rule(&self) -> Result<DependencyPolicy, ConfigError> {
+        let regex = |key: &str, pattern: &str| {
+            Regex::new(pattern).map_err(|e| ConfigError::InvalidRuleOption {
+                rule_id: self.id.clone(),
+                message: format!("the {} is not a valid regex: {}", key, e),
+            })
+        };
+        let mut rule = DependencyPolicy::new(
+            self.id.as_str(),
+            self.description.as_str(),
+            self.requirement,
+            regex("name", &self.name)?,
+        ).with_kinds(self.kinds.clone());
+        if let Some(ref members) = self.members {
+            rule = rule.with_members(regex("members", members)?);
+        }
+        if let Some(ref justification) = self.justification {
+            rule = rule.with_justification(justification.as_str());
+        }
+        if let Some(ref remediation) = self.remediation {
+            rule = rule.with_remediation(remediation.as_str());
+        }
+        Ok(rule)
+    }
+}
+
+impl CultureConfig {
+    /// Read and parse the configuration file at `culture_config_file_path`.
+    ///
+    /// Relative `plugins` paths are resolved against the directory that
+    /// contains the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError::ReadFailure` when the file cannot be read,
+    /// or a `ConfigError::ParseFailure` when its content is not valid.
+    pub fn from_file(culture_config_file_path: &Path) -> Result<Self, ConfigError> {
+        read_to_string(culture_config_file_path)
+            .map_err(|e| ConfigError::ReadFailure {
+                path: culture_config_file_path.display().to_string(),
+                cause: e.to_string(),
+            })?
+            .parse()
+            .map(|mut config: CultureConfig| {
+                if let Some(config_dir) = culture_config_file_path.parent() {
+                    for plugin in &mut config.plugins {
+                        if plugin.is_relative() {
+                            *plugin = config_dir.join(&plugin);
+                        }
+                    }
+                }
+                config
+            })
+    }
+
+    /// Produce the same `Rule`s as `default_rules`, with this configuration's
+    /// options and `severities` applied, followed by the declared
+    /// `file_rules` and `dependency_rules` and the `Rule`s of the `plugins`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError::InvalidRuleOption` when the options for a
+    /// `Rule` cannot be applied, a `severity` is configured for an `id`
+    /// that does not belong to any `Rule`, or a declared `Rule` is invalid
+    /// or reuses the `id` or `description` of another. Returns a
+    /// `ConfigError::PluginFailure` when one of the `plugins` cannot be loaded,
+    /// including when it does not describe itself within
+    /// `PLUGIN_DESCRIBE_TIMEOUT`.
+    pub fn configured_rules(&self) -> Result<Vec<Box<Rule>>, ConfigError> {
+        self.configured_rules_with(Vec::new()).map(|(rules, _)| rules)
+    }
+
+    /// Produce the `configured_rules` followed by those of the `discovered`
+    /// `Rule`s, such as the `ExternalRule`s of plugins found on the `PATH`,
+    /// that do not reuse the `id` or `description` of another `Rule`.
+    /// The `severities` apply to the `discovered` `Rule`s too.
+    ///
+    /// Returns the `Rule`s along with the ids of the `discovered` `Rule`s
+    /// left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `configured_rules`.
+    pub fn configured_rules_with(
+        &self,
+        discovered: Vec<Box<Rule>>,
+    ) -> Result<(Rules, Vec<String>), ConfigError> {
+        let mut rules: Vec<Box<Rule>> = Vec::new();
+        for rule in default_rules() {
+            let configured: Box<Rule> = match rule.id() {
+                "passes-multiple-tests" => match self.passes_multiple_tests.minimum_passing_tests {
+                    Some(minimum) => Box::new(PassesMultipleTests::new(minimum)),
+                    None => rule,
+                },
+                "has-continuous-integration-file" => {
+                    match self.has_continuous_integration_file.providers {
+                        Some(ref providers) => {
+                            Box::new(HasContinuousIntegrationFile::with_providers(providers)?)
+                        }
+                        None => rule,
+                    }
+                }
+                "builds-cleanly" if self.builds_cleanly != BuildsCleanlyConfig::default() => {
+                    Box::new(self.builds_cleanly.to_rule()?)
+                }
+                _ => rule,
+            };
+            rules.push(configured);
+        }
+        for file_rule in &self.file_rules {
+            push_declared_rule(&mut rules, Box::new(file_rule.to_rule()?))?;
+        }
+        for dependency_rule in &self.dependency_rules {
+            push_declared_rule(&mut rules, Box::new(dependency_rule.to_rule()?))?;
+        }
+        for plugin in &self.plugins {
+            let rule = ExternalRule::load_with_timeout(plugin.as_path(), PLUGIN_DESCRIBE_TIMEOUT)
+                .map_err(ConfigError::PluginFailure)?;
+            push_declared_rule(&mut rules, Box::new(rule))?;
+        }
+        let mut left_out: Vec<String> = Vec::new();
+        for rule in discovered {
+            if is_clashing(&rules, rule.as_ref()) {
+                left_out.push(rule.id().to_owned());
+            } else {
+                rules.push(rule);
+            }
+        }
+        Ok((self.apply_severities(rules)?, left_out))
+    }
+
+    fn apply_severities(&self, rules: Vec<Box<Rule>>) -> Result<Vec<Box<Rule>>, ConfigError> {
+        if let Some(unknown) = self
+            .severities
+            .keys()
+            .find(|id| !rules.iter().any(|r| r.id() == id.as_str()))
+        {
+            return Err(ConfigError::InvalidRuleOption {
+                rule_id: unknown.clone(),
+                message: String::from("a severity was configured, but no rule has this id"),
+            });
+        }
+        Ok(rules
+            .into_iter()
+            .map(|rule| match self.severities.get(rule.id()).cloned() {
+                Some(severity) => Box::new(WithSeverity { rule, severity }) as Box<Rule>,
+                None => rule,
+            })
+            .collect())
+    }
+
+    /// Produces the subset of the `available_rules` requested by this
+    /// configuration's `rules`, in the requested order, or all of the
+    /// `available_rules` when no `rules` were listed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `FilterError::RequestedRuleNotFound` error when one of the
+    /// requested `rules` does not match any of the `available_rules`.
+    pub fn enabled_rules<'r>(
+        &self,
+        available_rules: &'r [&Rule],
+    ) -> Result<Vec<&'r Rule>, FilterError> {
+        match self.rules {
+            Some(ref requested) => {
+                let requested_refs = requested.iter().map(|r| r.as_ref()).collect::<Vec<&str>>();
+                filter_to_requested_rules_by_description(available_rules, &requested_refs)
+            }
+            None => Ok(available_rules.to_vec()),
+        }
+    }
+}
+
+fn push_declared_rule(rules: &mut Vec<Box<Rule>>, declared: Box<Rule>) -> Result<(), ConfigError> {
+    if is_clashing(rules, declared.as_ref()) {
+        return Err(ConfigError::InvalidRuleOption {
+            rule_id: declared.id().to_owned(),
+            message: String::from("another rule has the same id or description"),
+        });
+    }
+    rules.push(declared);
+    Ok(())
+}
+
+/// The `Rule`s produced from a configuration
+type Rules = Vec<Box<Rule>>;
+
+/// Whether the `candidate` reuses the `id` or `description` of one of the
+/// `rules`
+fn is_clashing(rules: &[Box<Rule>], candidate: &Rule) -> bool {
+    rules
+        .iter()
+        .any(|r| r.id() == candidate.id() || r.description() == candidate.description())
+}
+
+/// A `Rule` whose `Severity` has been overridden by configuration
+#[derive(Debug)]
+struct WithSeverity {
+    rule: Box<Rule>,
+    severity: Severity,
+}
+
+impl Rule for WithSeverity {
+    fn description(&self) -> &str {
+        self.rule.description()
+    }
+
+    fn id(&self) -> &str {
+        self.rule.id()
+    }
+
+    fn justification(&self) -> Option<&str> {
+        self.rule.justification()
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        self.rule.remediation()
+    }
+
+    fn cost(&self) -> RuleCost {
+        self.rule.cost()
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn prerequisites(&self) -> Vec<&str> {
+        self.rule.prerequisites()
+    }
+
+    fn inputs(&self) -> Vec<PathBuf> {
+        self.rule.inputs()
+    }
+
+    fn configuration(&self) -> String {
+        self.rule.configuration()
+    }
+
+    fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+        self.rule.scaffold(project)
+    }
+
+    fn evaluate(&self, context: RuleContext) -> RuleOutcome {
+        self.rule.evaluate(context)
+    }
+}
+
+impl FromStr for CultureConfig {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| ConfigError::ParseFailure(e.to_string()))
+    }
+}
+
+/// If the supplied `initial_config_file` path is an extant file, just return
+/// that.
+///
+/// Otherwise, search the specified path and its ancestor directories for a file
+/// with a name matching the `DEFAULT_CULTURE_CONFIG_FILE_NAME`
+pub fn find_extant_culture_config_file(initial_config_file: &Path) -> Option<PathBuf> {
+    find_extant_file_named(initial_config_file, DEFAULT_CULTURE_CONFIG_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rules::test_support::{
+        execute_rule_against_project_dir, write_clean_src_main_file, write_package_cargo_toml,
+    };
+    use super::*;
+    use serde_json::{self, Value};
+    use std::fs::{create_dir_all, File};
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    const EXAMPLE: &str = r#"
+rules = ["passes-multiple-tests", "Should have a LICENSE file in the project directory."]
+
+[passes-multiple-tests]
+minimum-passing-tests = 10
+
+[has-continuous-integration-file]
+providers = ["travis", "gitlab-ci"]
+
+[builds-cleanly]
+allowed-lints = ["dead_code"]
+allowed-paths = ["src/generated/**"]
+maximum-warnings = 3
+
+[severities]
+has-license-file = "info"
+"#;
+
+    #[test]
+    fn parses_rules_and_options() {
+        let config: CultureConfig = EXAMPLE.parse().expect("Should parse");
+        assert_eq!(2, config.rules.as_ref().map_or(0, |r| r.len()));
+        assert_eq!(Some(10), config.passes_multiple_tests.minimum_passing_tests);
+        assert_eq!(
+            Some(vec![String::from("travis"), String::from("gitlab-ci")]),
+            config.has_continuous_integration_file.providers
+        );
+        assert_eq!(vec![String::from("dead_code")], config.builds_cleanly.allowed_lints);
+        assert_eq!(
+            vec![String::from("src/generated/**")],
+            config.builds_cleanly.allowed_paths
+        );
+        assert_eq!(Some(3), config.builds_cleanly.maximum_warnings);
+    }
+
+    #[test]
+    fn configured_rules_apply_clean_build_options() {
+        let config: CultureConfig = EXAMPLE.parse().expect("Should parse");
+        let rules = config.configured_rules().expect("Should configure");
+        let builds_cleanly = rules
+            .iter()
+            .find(|r| r.id() == "builds-cleanly")
+            .expect("Should be configured");
+        let configuration: Value =
+            serde_json::from_str(&builds_cleanly.configuration()).expect("Should be JSON");
+        assert_eq!(json!(["dead_code"]), configuration["allowed-lints"]);
+        assert_eq!(json!(["src/generated/**"]), configuration["allowed-paths"]);
+        assert_eq!(3, configuration["maximum-warnings"]);
+
+        let invalid: CultureConfig = "[builds-cleanly]\nallowed-paths = [\"src/***\"]"
+            .parse()
+            .expect("Should parse");
+        match invalid.configured_rules() {
+            Err(ConfigError::InvalidRuleOption { ref rule_id, .. }) => {
+                assert_eq!("builds-cleanly", rule_id)
+            }
+            other => panic!("Unexpected result {:?}", other.map(|r| r.len())),
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        for content in &[
+            "rulez = []",
+            "[passes-multiple-tests]\nminimum = 3",
+            "[has-rustfmt-file]\nenabled = true",
+        ] {
+            match content.parse::<CultureConfig>() {
+                Err(ConfigError::ParseFailure(_)) => (),
+                other => panic!("Unexpected result {:?} for {}", other, content),
+            }
+        }
+    }
+
+    #[test]
+    fn empty_config_enables_every_default_rule_unchanged() {
+        let config: CultureConfig = "".parse().expect("Should parse");
+        let rules = config.configured_rules().expect("Should configure");
+        let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+        let enabled = config.enabled_rules(&rules_refs).expect("Should filter");
+        let defaults = default_rules();
+        assert_eq!(defaults.len(), enabled.len());
+        for (rule, default) in enabled.iter().zip(&defaults) {
+            assert_eq!(default.id(), rule.id());
+            assert_eq!(default.description(), rule.description());
+            assert_eq!(default.severity(), rule.severity());
+            assert_eq!(default.cost(), rule.cost());
+            assert_eq!(default.prerequisites(), rule.prerequisites());
+            assert_eq!(default.inputs(), rule.inputs());
+        }
+    }
+
+    #[test]
+    fn configured_rules_apply_options_and_enabled_rules_keep_requested_order() {
+        let config: CultureConfig = EXAMPLE.parse().expect("Should parse");
+        let rules = config.configured_rules().expect("Should configure");
+        let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+        let enabled = config.enabled_rules(&rules_refs).expect("Should filter");
+        assert_eq!(
+            vec!["passes-multiple-tests", "has-license-file"],
+            enabled.iter().map(|r| r.id()).collect::<Vec<&str>>()
+        );
+        assert_eq!(Severity::Error, enabled[0].severity());
+        assert_eq!(Severity::Info, enabled[1].severity());
+    }
+
+    #[test]
+    fn configured_rules_evaluate_with_the_configured_minimum_passing_tests() {
+        if ::std::env::var("CARGO_CULTURE_TEST_RECURSION_BUSTER").is_ok() {
+            return;
+        }
+        let config: CultureConfig = EXAMPLE.parse().expect("Should parse");
+        let rules = config.configured_rules().expect("Should configure");
+        let passes_multiple_tests = rules
+            .iter()
+            .find(|r| r.id() == "passes-multiple-tests")
+            .expect("Should be configured");
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let capture =
+            execute_rule_against_project_dir(dir.path(), passes_multiple_tests.as_ref(), false);
+        assert_eq!(RuleOutcome::Failure, capture.outcome);
+        assert!(capture.findings[0].message.contains("at least 10 are required"));
+    }
+
+    #[test]
+    fn configured_rules_reject_severities_of_unknown_rules() {
+        let config: CultureConfig = "[severities]\nhas-licence-file = \"warning\""
+            .parse()
+            .expect("Should parse");
+        match config.configured_rules() {
+            Err(ConfigError::InvalidRuleOption { ref rule_id, .. }) => {
+                assert_eq!("has-licence-file", rule_id)
+            }
+            other => panic!("Unexpected result {:?}", other.map(|r| r.len())),
+        }
+        assert!("[severities]\nhas-license-file = \"fatal\"".parse::<CultureConfig>().is_err());
+    }
+
+    #[test]
+    fn configured_rules_reject_unknown_providers() {
+        let config: CultureConfig = "[has-continuous-integration-file]\nproviders = [\"jenkins\"]"
+            .parse()
+            .expect("Should parse");
+        match config.configured_rules() {
+            Err(ConfigError::InvalidRuleOption { ref rule_id, .. }) => {
+                assert_eq!("has-continuous-integration-file", rule_id)
+            }
+            other => panic!("Unexpected result {:?}", other.map(|r| r.len())),
+        }
+    }
+
+    #[test]
+    fn configured_rules_include_declared_file_rules() {
+        let config: CultureConfig = r#"
+rules = ["has-codeowners-file"]
+
+[[file-rules]]
+id = "has-codeowners-file"
+description = "Should have a CODEOWNERS file."
+file-name = "^CODEOWNERS$"
+scopes = ["github", "recursive"]
+require-non-empty = false
+
+[severities]
+has-codeowners-file = "warning"
+"#
+        .parse()
+        .expect("Should parse");
+        let rules = config.configured_rules().expect("Should configure");
+        assert_eq!(default_rules().len() + 1, rules.len());
+        let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+        let enabled = config.enabled_rules(&rules_refs).expect("Should filter");
+        assert_eq!(1, enabled.len());
+        assert_eq!("Should have a CODEOWNERS file.", enabled[0].description());
+        assert_eq!(Severity::Warning, enabled[0].severity());
+
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let capture = execute_rule_against_project_dir(dir.path(), enabled[0], false);
+        assert_eq!(RuleOutcome::Failure, capture.outcome);
+
+        let github_dir = dir.path().join(".github");
+        create_dir_all(&github_dir).expect("Could not make .github dir");
+        File::create(github_dir.join("CODEOWNERS")).expect("Could not create file");
+        let capture = execute_rule_against_project_dir(dir.path(), enabled[0], false);
+        assert_eq!(RuleOutcome::Success, capture.outcome);
+
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let nested = dir.path().join("ci").join("config");
+        create_dir_all(&nested).expect("Could not make nested dirs");
+        File::create(nested.join("CODEOWNERS")).expect("Could not create file");
+        let capture = execute_rule_against_project_dir(dir.path(), enabled[0], false);
+        assert_eq!(RuleOutcome::Success, capture.outcome);
+    }
+
+    #[test]
+    fn configured_rules_reject_invalid_file_rules() {
+        for content in &[
+            "[[file-rules]]\nid = \"x\"\ndescription = \"X\"\nfile-name = \"(\"",
+            "[[file-rules]]\nid = \"x\"\ndescription = \"X\"\nfile-name = \"x\"\nscopes = []",
+            "[[file-rules]]\nid = \"has-license-file\"\ndescription = \"X\"\nfile-name = \"x\"",
+        ] {
+            let config: CultureConfig = content.parse().expect("Should parse");
+            match config.configured_rules() {
+                Err(ConfigError::InvalidRuleOption { .. }) => (),
+                other => panic!("Unexpected result {:?} for {}", other.map(|r| r.len()), content),
+            }
+        }
+    }
+
+    #[test]
+    fn configured_rules_include_declared_dependency_rules() {
+        let config: CultureConfig = r#"
+[[dependency-rules]]
+id = "dev-depends-on-proptest"
+description = "Every member should dev-depend on proptest."
+requirement = "required"
+name = "^proptest$"
+kinds = ["dev"]
+members = "^kid$"
+
+[[dependency-rules]]
+id = "depends-on-proptest"
+description = "Every member should depend on proptest."
+requirement = "required"
+name = "^proptest$"
+kinds = ["normal"]
+members = "^kid$"
+"#
+        .parse()
+        .expect("Should parse");
+        let rules = config.configured_rules().expect("Should configure");
+        let declared = &rules[rules.len() - 2..];
+        assert_eq!("dev-depends-on-proptest", declared[0].id());
+        assert_eq!("depends-on-proptest", declared[1].id());
+
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), Some("proptest"));
+        write_clean_src_main_file(dir.path());
+        let capture = execute_rule_against_project_dir(dir.path(), declared[0].as_ref(), false);
+        assert_eq!(RuleOutcome::Success, capture.outcome);
+        let capture = execute_rule_against_project_dir(dir.path(), declared[1].as_ref(), false);
+        assert_eq!(RuleOutcome::Failure, capture.outcome);
+        assert_eq!(1, capture.findings.len());
+
+        for content in &[
+            "[[dependency-rules]]\nid = \"x\"\ndescription = \"X\"\nrequirement = \"forbidden\"\n\
+             name = \"x\"\nmembers = \"(\"",
+            "[[dependency-rules]]\nid = \"x\"\ndescription = \"X\"\nrequirement = \"forbidden\"\n\
+             name = \"x\"\n[[file-rules]]\nid = \"x\"\ndescription = \"Y\"\nfile-name = \"x\"",
+        ] {
+            let config: CultureConfig = content.parse().expect("Should parse");
+            match config.configured_rules() {
+                Err(ConfigError::InvalidRuleOption { .. }) => (),
+                other => panic!("Unexpected result {:?} for {}", other.map(|r| r.len()), content),
+            }
+        }
+        assert!(
+            "[[dependency-rules]]\nid = \"x\"\ndescription = \"X\"\nrequirement = \"maybe\"\n\
+             name = \"x\""
+                .parse::<CultureConfig>()
+                .is_err()
+        );
+    }
+
+    #[derive(Debug)]
+    struct DiscoveredRule(&'static str);
+
+    impl Rule for DiscoveredRule {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "Should be discovered."
+        }
+
+        fn evaluate(&self, _context: ::RuleContext) -> ::RuleOutcome {
+            ::RuleOutcome::Success
+        }
+    }
+
+    #[test]
+    fn configured_rules_with_leave_out_clashing_discovered_rules() {
+        let config: CultureConfig = "[severities]\ndiscovered = \"warning\""
+            .parse()
+            .expect("Should parse");
+        let (rules, left_out) = config
+            .configured_rules_with(vec![
+                Box::new(DiscoveredRule("has-license-file")),
+                Box::new(DiscoveredRule("discovered")),
+            ]).expect("Should configure");
+        assert_eq!(default_rules().len() + 1, rules.len());
+        let discovered = rules.last().expect("Should have rules");
+        assert_eq!("discovered", discovered.id());
+        assert_eq!(Severity::Warning, discovered.severity());
+        assert_eq!(vec![String::from("has-license-file")], left_out);
+    }
+
+    #[test]
+    fn find_extant_config_file_from_dir_ancestor_success() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let subdir = dir.path().join("kid").join("grandkid");
+        create_dir_all(&subdir).expect("Could not make subdirs");
+        let file_path = dir.path().join(DEFAULT_CULTURE_CONFIG_FILE_NAME);
+        File::create(&file_path)
+            .and_then(|mut f| f.write_all(EXAMPLE.as_bytes()))
+            .expect("Could not write config file");
+
+        assert_eq!(Some(file_path.clone()), find_extant_culture_config_file(&subdir));
+        let config = CultureConfig::from_file(&file_path).expect("Should read");
+        assert_eq!(Some(10), config.passes_multiple_tests.minimum_passing_tests);
+    }
+
+    #[test]
+    fn from_file_resolves_plugins_against_the_file_dir() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let file_path = dir.path().join(DEFAULT_CULTURE_CONFIG_FILE_NAME);
+        File::create(&file_path)
+            .and_then(|mut f| f.write_all(b"plugins = [\"tools/check\", \"/opt/check\"]"))
+            .expect("Could not write config file");
+        let config = CultureConfig::from_file(&file_path).expect("Should read");
+        assert_eq!(
+            vec![dir.path().join("tools").join("check"), PathBuf::from("/opt/check")],
+            config.plugins
+        );
+        match config.configured_rules() {
+            Err(ConfigError::PluginFailure(_)) => (),
+            other => panic!("Unexpected result {:?}", other.map(|r| r.len())),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn configured_plugins_that_will_not_describe_themselves_in_time_fail_to_load() {
+        use std::fs::set_permissions;
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::Instant;
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let program = dir.path().join("sleepy");
+        File::create(&program)
+            .and_then(|mut f| f.write_all(b"#!/bin/sh\nsleep 30\n"))
+            .expect("Could not write plugin");
+        set_permissions(&program, PermissionsExt::from_mode(0o755))
+            .expect("Could not make plugin executable");
+        let config = CultureConfig {
+            plugins: vec![program],
+            ..CultureConfig::default()
+        };
+        let start = Instant::now();
+        match config.configured_rules() {
+            Err(ConfigError::PluginFailure(PluginError::ProtocolViolation { .. })) => (),
+            other => panic!("Unexpected result {:?}", other.map(|r| r.len())),
+        }
+        assert!(start.elapsed() < PLUGIN_DESCRIBE_TIMEOUT * 2);
+    }
+}
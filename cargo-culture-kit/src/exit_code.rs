@@ -1,10 +1,14 @@
 //! Program exit code hinting through the `ExitCode` trait
 
 use super::CheckError;
+use super::CultureReport;
 use super::OutcomeStats;
 use super::OutcomesByDescription;
 use super::RuleOutcome;
 use checklist::FilterError;
+use config::ConfigError;
+use plugin::PluginError;
+use scaffold::ScaffoldError;
 use failure;
 
 /// A means of genericizing expected process exit code
@@ -16,12 +20,16 @@ pub trait ExitCode {
     fn exit_code(&self) -> i32;
 }
 
+/// `RuleOutcome::Skipped` only describes a single `Rule`, so it has an exit
+/// code of its own. The aggregate of a check is never skipped: one in which
+/// every `Rule` was skipped is `RuleOutcome::Undetermined`.
 impl ExitCode for RuleOutcome {
     fn exit_code(&self) -> i32 {
         match *self {
             RuleOutcome::Success => 0,
             RuleOutcome::Failure => 1,
             RuleOutcome::Undetermined => 2,
+            RuleOutcome::Skipped => 3,
         }
     }
 }
@@ -38,10 +46,17 @@ impl ExitCode for OutcomesByDescription {
     }
 }
 
+impl ExitCode for CultureReport {
+    fn exit_code(&self) -> i32 {
+        OutcomeStats::from(self).exit_code()
+    }
+}
+
 impl ExitCode for CheckError {
     fn exit_code(&self) -> i32 {
         match *self {
             CheckError::PrintOutputFailure { .. } => 11,
+            CheckError::CyclicPrerequisites { .. } => 12,
             _ => 10,
         }
     }
@@ -52,11 +67,44 @@ impl ExitCode for FilterError {
         match *self {
             FilterError::RuleChecklistReadError(_) => 21,
             FilterError::RequestedRuleNotFound { .. } => 22,
+            FilterError::AmbiguousRuleRequest { .. } => 23,
             _ => 20,
         }
     }
 }
 
+impl ExitCode for ScaffoldError {
+    fn exit_code(&self) -> i32 {
+        match *self {
+            ScaffoldError::FileAlreadyExists(_) => 31,
+            ScaffoldError::WriteFailure { .. } => 32,
+            _ => 30,
+        }
+    }
+}
+
+impl ExitCode for ConfigError {
+    fn exit_code(&self) -> i32 {
+        match *self {
+            ConfigError::ReadFailure { .. } => 41,
+            ConfigError::ParseFailure(_) => 42,
+            ConfigError::InvalidRuleOption { .. } => 43,
+            ConfigError::PluginFailure(_) => 44,
+            _ => 40,
+        }
+    }
+}
+
+impl ExitCode for PluginError {
+    fn exit_code(&self) -> i32 {
+        match *self {
+            PluginError::LaunchFailure { .. } => 51,
+            PluginError::ProtocolViolation { .. } => 52,
+            _ => 50,
+        }
+    }
+}
+
 impl ExitCode for failure::Error {
     fn exit_code(&self) -> i32 {
         1
@@ -75,3 +123,24 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_rules_do_not_set_the_exit_code_of_a_check() {
+        assert_eq!(3, RuleOutcome::Skipped.exit_code());
+        let all_skipped = OutcomeStats {
+            skipped_count: 2,
+            ..OutcomeStats::default()
+        };
+        assert_eq!(2, all_skipped.exit_code());
+        let some_skipped = OutcomeStats {
+            success_count: 1,
+            skipped_count: 2,
+            ..OutcomeStats::default()
+        };
+        assert_eq!(0, some_skipped.exit_code());
+    }
+}
@@ -1,5 +1,5 @@
 //! File discovery and inspection utilities for use in implementing `Rule`s
-use super::RuleOutcome;
+use super::{Finding, RuleOutcome};
 use cargo_metadata::Metadata as CargoMetadata;
 use regex::Regex;
 use std::convert::From;
@@ -19,18 +19,24 @@ pub fn shallow_scan_project_dir_for_nonempty_file_name_match(
 }
 
 pub fn find_nonempty_child_file(regex: &Regex, project_dir: &Path) -> RuleOutcome {
-    if !project_dir.is_dir() {
+    find_child_file(regex, project_dir, true)
+}
+
+/// Look for a file directly within `dir` with a name matching `regex`,
+/// which must also have some content when `require_nonempty` is set
+pub fn find_child_file(regex: &Regex, dir: &Path, require_nonempty: bool) -> RuleOutcome {
+    if !dir.is_dir() {
         return RuleOutcome::Undetermined;
     }
     let mut entry_unreadable = false;
-    let dir = match read_dir(project_dir) {
+    let entries = match read_dir(dir) {
         Ok(d) => d,
         Err(_) => {
             return RuleOutcome::Undetermined;
         }
     };
 
-    for entry in dir {
+    for entry in entries {
         match entry {
             Ok(entry) => {
                 let path = entry.path();
@@ -41,7 +47,9 @@ pub fn find_nonempty_child_file(regex: &Regex, project_dir: &Path) -> RuleOutcom
                     .and_then(|name| name.to_str())
                     .map(|name| regex.is_match(name))
                     .unwrap_or(false);
-                if name_matches && path.metadata().ok().map(|m| m.len() > 0).unwrap_or(false) {
+                let content_suffices = !require_nonempty
+                    || path.metadata().ok().map(|m| m.len() > 0).unwrap_or(false);
+                if name_matches && content_suffices {
                     return RuleOutcome::Success;
                 }
             }
@@ -57,6 +65,43 @@ pub fn find_nonempty_child_file(regex: &Regex, project_dir: &Path) -> RuleOutcom
     }
 }
 
+/// Look for a file anywhere beneath `dir` with a name matching `regex`, as in
+/// `find_child_file`.
+///
+/// The `target` and `.git` directories are not searched.
+pub fn find_descendant_file(regex: &Regex, dir: &Path, require_nonempty: bool) -> RuleOutcome {
+    let mut outcome = find_child_file(regex, dir, require_nonempty);
+    if outcome == RuleOutcome::Success {
+        return outcome;
+    }
+    let entries = match read_dir(dir) {
+        Ok(d) => d,
+        Err(_) => return RuleOutcome::Undetermined,
+    };
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => {
+                outcome = RuleOutcome::Undetermined;
+                continue;
+            }
+        };
+        let skipped = path
+            .file_name()
+            .map(|name| name == "target" || name == ".git")
+            .unwrap_or(true);
+        if skipped || !path.is_dir() {
+            continue;
+        }
+        match find_descendant_file(regex, &path, require_nonempty) {
+            RuleOutcome::Success => return RuleOutcome::Success,
+            RuleOutcome::Undetermined => outcome = RuleOutcome::Undetermined,
+            RuleOutcome::Failure | RuleOutcome::Skipped => (),
+        }
+    }
+    outcome
+}
+
 pub fn search_manifest_and_workspace_dir_for_nonempty_file_name_match(
     regex: &Regex,
     manifest_path: &Path,
@@ -74,13 +119,42 @@ pub fn search_manifest_and_workspace_dir_for_nonempty_file_name_match(
         Some(ref metadata) => {
             match search_metadata_workspace_root_for_file_name_match(regex, metadata) {
                 RuleOutcome::Success => RuleOutcome::Success,
-                RuleOutcome::Failure | RuleOutcome::Undetermined => outcome_in_given_manifest_path,
+                _ => outcome_in_given_manifest_path,
             }
         }
         _ => outcome_in_given_manifest_path,
     }
 }
 
+/// The directories inspected by
+/// `search_manifest_and_workspace_dir_for_nonempty_file_name_match`
+pub fn manifest_and_workspace_dirs(
+    manifest_path: &Path,
+    maybe_metadata: &Option<CargoMetadata>,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(manifest_dir) = manifest_path.parent() {
+        dirs.push(manifest_dir.to_path_buf());
+    }
+    if let Some(ref metadata) = *maybe_metadata {
+        let workspace_dir = PathBuf::from(&metadata.workspace_root);
+        if !metadata.workspace_root.is_empty() && !dirs.contains(&workspace_dir) {
+            dirs.push(workspace_dir);
+        }
+    }
+    dirs
+}
+
+/// Describe an unsuccessful search of `dirs` for a file name matching `regex`
+pub fn file_search_finding(regex: &Regex, dirs: &[PathBuf]) -> Finding {
+    let dir_names: Vec<String> = dirs.iter().map(|d| d.display().to_string()).collect();
+    Finding::new(format!(
+        "Searched {} for a non-empty file with a name matching `{}`",
+        dir_names.join(", "),
+        regex.as_str()
+    ))
+}
+
 fn search_metadata_workspace_root_for_file_name_match(
     regex: &Regex,
     metadata: &CargoMetadata,
@@ -190,6 +264,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn find_descendant_file_skips_target_dirs() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let r = Regex::new(r"^deny\.toml$").expect("Could not make regex");
+        let nested = dir.path().join("a").join("b");
+        let target = dir.path().join("target");
+        create_dir_all(&nested).expect("Could not create nested dirs");
+        create_dir_all(&target).expect("Could not create target dir");
+        File::create(target.join("deny.toml"))
+            .and_then(|mut f| f.write_all(b"[bans]"))
+            .expect("Could not write file");
+        assert_eq!(RuleOutcome::Failure, find_descendant_file(&r, dir.path(), true));
+
+        File::create(nested.join("deny.toml")).expect("Could not create file");
+        assert_eq!(RuleOutcome::Failure, find_descendant_file(&r, dir.path(), true));
+        assert_eq!(RuleOutcome::Success, find_descendant_file(&r, dir.path(), false));
+    }
+
     #[test]
     fn shallow_scan_follows_file_lifecycle() {
         let dir = tempdir().expect("Failed to make a temp dir");
@@ -2,11 +2,18 @@
 //! project-level rules about Rust best practices.
 //!
 //! The primary function entry points are `check_culture` and
-//! `check_culture_default`.
+//! `check_culture_default`. `check_culture_with_jobs` evaluates
+//! independent `Rule`s concurrently, and `check_culture_with_options`
+//! additionally limits how long their subprocesses may run.
 //!
 //! The core trait is `Rule`, which represents a single project-level property
 //! that has a clear description and can be checked.
 //!
+//! The progress and results of a run are presented through a `Reporter`.
+//! `TextReporter` prints human-oriented prose, and `JsonReporter`
+//! produces a machine-readable document. The results are also returned
+//! to the caller as a `CultureReport`.
+//!
 //! # Examples
 //!
 //! `check_culture_default` is the easiest way to get started,
@@ -15,20 +22,20 @@
 //! `default_rules()` function.
 //!
 //! ```no_run
-//! use cargo_culture_kit::{check_culture_default, IsSuccess, OutcomeStats};
-//! use std::path::PathBuf;
+//! use cargo_culture_kit::{check_culture_default, IsSuccess, OutcomeStats,
+//! TextReporter}; use std::path::PathBuf;
 //!
 //! let cargo_manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
 //!         .join("Cargo.toml");
 //!
 //! let verbose = false;
 //!
-//! let outcomes = check_culture_default(
-//!     cargo_manifest, verbose, &mut std::io::stdout()
+//! let report = check_culture_default(
+//!     cargo_manifest, verbose, &mut TextReporter::new(std::io::stdout())
 //!     )
 //!     .expect("Unexpected trouble checking culture rules:");
 //!
-//! let stats = OutcomeStats::from(outcomes);
+//! let stats = OutcomeStats::from(report);
 //! assert!(stats.is_success());
 //! assert_eq!(stats.fail_count, 0);
 //! assert_eq!(stats.undetermined_count, 0);
@@ -39,7 +46,7 @@
 //!
 //! ```
 //! use cargo_culture_kit::{check_culture, IsSuccess, OutcomeStats,
-//! HasLicenseFile}; use std::path::PathBuf;
+//! HasLicenseFile, TextReporter}; use std::path::PathBuf;
 //!
 //! let rule = HasLicenseFile::default();
 //! let cargo_manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -47,12 +54,13 @@
 //!
 //! let verbose = false;
 //!
-//! let outcomes = check_culture(
-//!     cargo_manifest, verbose, &mut std::io::stdout(), &[&rule]
+//! let report = check_culture(
+//!     cargo_manifest, verbose, &mut TextReporter::new(std::io::stdout()),
+//!     &[&rule]
 //!     )
 //!     .expect("Unexpected trouble checking culture rules: ");
 //!
-//! let stats = OutcomeStats::from(outcomes);
+//! let stats = OutcomeStats::from(report);
 //! assert!(stats.is_success());
 //! assert_eq!(stats.success_count, 1);
 //! assert_eq!(stats.fail_count, 0);
@@ -75,35 +83,74 @@ extern crate proptest;
 #[macro_use]
 extern crate lazy_static;
 
+#[macro_use]
+extern crate serde_derive;
+
 extern crate cargo_metadata;
 extern crate colored;
+extern crate glob;
 
+#[cfg(unix)]
+extern crate libc;
 extern crate regex;
+extern crate serde;
+#[macro_use]
+extern crate serde_json;
+extern crate sha2;
+extern crate toml;
 
+mod cache;
 mod file;
+mod process;
 
 pub mod checklist;
+pub mod config;
 pub mod exit_code;
+pub mod plugin;
+pub mod report;
+pub mod reporter;
 pub mod rules;
+pub mod scaffold;
 
 pub use checklist::{
     filter_to_requested_rules_by_description, filter_to_requested_rules_from_checklist_file,
-    find_extant_culture_file, FilterError, DEFAULT_CULTURE_CHECKLIST_FILE_NAME,
+    find_extant_culture_file, find_rule, format_checklist, FilterError,
+    DEFAULT_CULTURE_CHECKLIST_FILE_NAME,
+};
+pub use cache::CULTURE_CACHE_FILE_NAME;
+pub use config::{
+    find_extant_culture_config_file, ConfigError, CultureConfig, DEFAULT_CULTURE_CONFIG_FILE_NAME,
 };
 pub use exit_code::ExitCode;
+pub use plugin::{
+    find_plugins_in_dirs, find_plugins_on_path, ExternalRule, PluginDescription, PluginError,
+    PluginEvaluation, PluginRequest, PLUGIN_DESCRIBE_TIMEOUT, PLUGIN_PREFIX,
+};
+pub use process::{cancel_subprocesses, subprocesses_cancelled};
+pub use report::{CultureReport, RuleReport};
+pub use reporter::{
+    check_culture_json, write_json_report, HtmlReporter, JsonReport, JsonReporter,
+    JsonRuleEvaluation, JunitReporter, MarkdownReporter, Reporter, SarifReporter, TapReporter,
+    TextReporter,
+};
 pub use rules::{
-    default_rules, BuildsCleanlyWithoutWarningsOrErrors, CargoMetadataReadable,
-    HasContinuousIntegrationFile, HasContributingFile, HasLicenseFile, HasReadmeFile,
-    HasRustfmtFile, PassesMultipleTests, Rule, RuleContext, RuleOutcome,
-    UsesPropertyBasedTestLibrary,
+    default_rules, BuildsCleanlyWithoutWarningsOrErrors, CargoMetadataReadable, DependencyPolicy,
+    DependencyRequirement, FileSearchScope, Finding, HasContinuousIntegrationFile,
+    HasContributingFile, HasLicenseFile, HasMatchingFile, HasReadmeFile, HasRustfmtFile,
+    PassesMultipleTests, Rule, RuleContext, RuleCost, RuleOutcome, Severity,
+    UsesPropertyBasedTestLibrary, CULTURE_TARGET_DIR_NAME,
 };
+pub use scaffold::{plan_scaffolds, PackageInfo, ProjectInfo, Scaffold, ScaffoldError};
 
 pub use cargo_metadata::Metadata as CargoMetadata;
-use colored::*;
+use cache::ResultCache;
 use std::borrow::Borrow;
-use std::collections::HashMap;
-use std::io::Write;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Top-level error variants for what can go wrong with checking culture rules.
 ///
@@ -114,12 +161,19 @@ pub enum CheckError {
     #[fail(
         display = "There was an error while attempting to print {} to the output writer.", topic
     )]
-    /// Failure during writing human-oriented textual content to an output
-    /// `Write` instance.
+    /// Failure during writing human-oriented textual content or a report
+    /// document to an output `Write` instance.
     PrintOutputFailure {
         /// The sort of content that was failed to be written
         topic: &'static str,
     },
+    #[fail(display = "The prerequisites of these rules form a cycle: {:?}", ids)]
+    /// Some `Rule`s could not be ordered, since each is a direct or indirect
+    /// prerequisite of itself.
+    CyclicPrerequisites {
+        /// The `id`s of the `Rule`s that could not be ordered
+        ids: Vec<String>,
+    },
     /// Destructuring should not be exhaustive.
     ///
     /// This enum may grow additional variants, so this hidden variant
@@ -137,25 +191,26 @@ pub enum CheckError {
 /// # Examples
 ///
 /// ```no_run
-/// use cargo_culture_kit::{check_culture_default, IsSuccess, OutcomeStats};
-/// use std::path::PathBuf;
+/// use cargo_culture_kit::{check_culture_default, IsSuccess, OutcomeStats,
+/// TextReporter}; use std::path::PathBuf;
 ///
 /// let cargo_manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
 ///         .join("Cargo.toml");
 ///
 /// let verbose = false;
 ///
-/// let outcomes = check_culture_default(
-///                 cargo_manifest, verbose, &mut std::io::stdout())
+/// let report = check_culture_default(
+///                 cargo_manifest, verbose,
+///                 &mut TextReporter::new(std::io::stdout()))
 ///     .expect("Unexpected trouble checking culture rules:");
 ///
-/// for (description, outcome) in &outcomes {
+/// for rule in &report {
 ///     println!(
-///              "For this project: {} had an outcome of {:?}",
-///              description, outcome);
+///              "For this project: {} had an outcome of {:?} after {:?}",
+///              rule.description, rule.outcome, rule.duration);
 /// }
 ///
-/// let stats = OutcomeStats::from(outcomes);
+/// let stats = OutcomeStats::from(report);
 /// assert!(stats.is_success());
 /// assert_eq!(stats.fail_count, 0);
 /// assert_eq!(stats.undetermined_count, 0);
@@ -163,20 +218,22 @@ pub enum CheckError {
 ///
 /// # Errors
 ///
-/// Returns an error if the program cannot write to the supplied `print_output`
-/// instance.
-pub fn check_culture_default<P: AsRef<Path>, W: Write>(
+/// Returns an error if the supplied `reporter` fails, most commonly
+/// because it cannot write to its output.
+pub fn check_culture_default<P: AsRef<Path>, R: Reporter + ?Sized>(
     cargo_manifest_file_path: P,
     verbose: bool,
-    print_output: &mut W,
-) -> Result<OutcomesByDescription, CheckError> {
+    reporter: &mut R,
+) -> Result<CultureReport, CheckError> {
     let rules = default_rules();
     let rule_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
-    check_culture(cargo_manifest_file_path, verbose, print_output, &rule_refs)
+    check_culture(cargo_manifest_file_path, verbose, reporter, &rule_refs)
 }
 
 /// Given a set of `Rule`s, evaluate the rules
-/// and produce a summary report of the rule outcomes.
+/// and produce a `CultureReport` of the rule outcomes,
+/// in the order the `rules` were supplied, except that each `Rule` follows
+/// its `prerequisites`.
 ///
 /// Primary entry point for this library.
 ///
@@ -186,18 +243,21 @@ pub fn check_culture_default<P: AsRef<Path>, W: Write>(
 /// `verbose` controls whether or not to produce additional human-readable
 /// reporting.
 ///
-/// `print_output` is the `Write` instance where `Rule` evaluation summaries
-/// are printed, as well as the location where `verbose` content may be dumped.
-/// `&mut std::io::stdout()` is a common instance used by non-test applications.
+/// `reporter` is notified as the run progresses, and is responsible for
+/// presenting `Rule` evaluation summaries as well as any `verbose` content.
+/// `&mut TextReporter::new(std::io::stdout())` is a common instance used by
+/// non-test applications.
 ///
 /// `rules` is the complete set of `Rule` instances which will be evaluated for
-/// the project specified by `cargo_manifest_file_path`.
+/// the project specified by `cargo_manifest_file_path`. A `Rule` whose
+/// `prerequisites` are not upheld is not evaluated, and is reported as
+/// `RuleOutcome::Skipped` instead.
 ///
 /// # Examples
 ///
 /// ```
 /// use cargo_culture_kit::{check_culture, IsSuccess, OutcomeStats,
-/// HasLicenseFile}; use std::path::PathBuf;
+/// HasLicenseFile, TextReporter}; use std::path::PathBuf;
 ///
 /// let rule = HasLicenseFile::default();
 /// let cargo_manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -205,11 +265,11 @@ pub fn check_culture_default<P: AsRef<Path>, W: Write>(
 ///
 /// let verbose = false;
 ///
-/// let outcomes = check_culture(cargo_manifest, verbose, &mut
-/// std::io::stdout(),     &[&rule])
+/// let report = check_culture(cargo_manifest, verbose,
+///     &mut TextReporter::new(std::io::stdout()), &[&rule])
 ///     .expect("Unexpected trouble checking culture rules: ");
 ///
-/// let stats = OutcomeStats::from(outcomes);
+/// let stats = OutcomeStats::from(report);
 /// assert!(stats.is_success());
 /// assert_eq!(stats.success_count, 1);
 /// assert_eq!(stats.fail_count, 0);
@@ -218,95 +278,509 @@ pub fn check_culture_default<P: AsRef<Path>, W: Write>(
 ///
 /// # Errors
 ///
-/// Returns an error if the program cannot write to the supplied `print_output`
-/// instance.
-pub fn check_culture<P: AsRef<Path>, W: Write>(
+/// Returns an error if the `prerequisites` of the `rules` form a cycle, or
+/// if the supplied `reporter` fails, most commonly because it cannot write
+/// to its output.
+pub fn check_culture<P: AsRef<Path>, R: Reporter + ?Sized>(
     cargo_manifest_file_path: P,
     verbose: bool,
-    print_output: &mut W,
+    reporter: &mut R,
     rules: &[&Rule],
-) -> Result<OutcomesByDescription, CheckError> {
-    let metadata_option =
-        read_cargo_metadata(cargo_manifest_file_path.as_ref(), verbose, print_output)?;
-    let outcomes = evaluate_rules(
-        cargo_manifest_file_path.as_ref(),
+) -> Result<CultureReport, CheckError> {
+    check_culture_with_options(
+        cargo_manifest_file_path,
         verbose,
-        &metadata_option,
-        print_output,
+        &CheckOptions::default(),
+        reporter,
         rules,
-    )?;
-    print_outcome_stats(&outcomes, print_output)?;
-    Ok(outcomes)
+    )
 }
 
-fn read_cargo_metadata<P: AsRef<Path>, W: Write>(
+/// Like `check_culture`, but evaluates up to `jobs` `Rule`s concurrently.
+///
+/// `RuleCost::Cheap` `Rule`s are evaluated first, and alongside one
+/// another, while at most one `RuleCost::Expensive` `Rule` is evaluated at
+/// a time, since such `Rule`s typically run `cargo` against the same
+/// target directory. A `Rule` is never evaluated before its
+/// `prerequisites`. A `jobs` of 0 is treated as 1.
+///
+/// The `reporter` is notified of each `Rule`, and the `CultureReport` is
+/// ordered, just as for `check_culture`, regardless of the order in which
+/// the evaluations complete.
+///
+/// # Errors
+///
+/// Returns an error if the `prerequisites` of the `rules` form a cycle, or
+/// if the supplied `reporter` fails, most commonly because it cannot write
+/// to its output.
+pub fn check_culture_with_jobs<P: AsRef<Path>, R: Reporter + ?Sized>(
     cargo_manifest_file_path: P,
     verbose: bool,
-    print_output: &mut W,
+    jobs: usize,
+    reporter: &mut R,
+    rules: &[&Rule],
+) -> Result<CultureReport, CheckError> {
+    check_culture_with_options(
+        cargo_manifest_file_path,
+        verbose,
+        &CheckOptions {
+            jobs,
+            ..CheckOptions::default()
+        },
+        reporter,
+        rules,
+    )
+}
+
+/// Tuning for how `check_culture_with_options` evaluates `Rule`s
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckOptions {
+    /// The number of `Rule`s that may be evaluated concurrently,
+    /// as for `check_culture_with_jobs`
+    pub jobs: usize,
+    /// How long the subprocesses of any one `Rule`, such as `cargo build`,
+    /// may run, measured from the start of that `Rule`'s evaluation
+    pub rule_timeout: Option<Duration>,
+    /// How long the subprocesses of all of the `Rule`s may run,
+    /// measured from the start of the check
+    pub timeout: Option<Duration>,
+    /// Whether to reuse the outcomes of `Rule`s whose `inputs` are unchanged
+    /// since an earlier check with `cache` set, and to remember the outcomes
+    /// of this check for the next
+    pub cache: bool,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        CheckOptions {
+            jobs: 1,
+            rule_timeout: None,
+            timeout: None,
+            cache: false,
+        }
+    }
+}
+
+/// Like `check_culture_with_jobs`, but with the concurrency and the time
+/// limits of the check supplied as `options`.
+///
+/// When a `Rule`'s time runs out, or `cancel_subprocesses` is called, the
+/// subprocess it is waiting upon is killed along with any processes that
+/// subprocess started, and the `Rule` reports `RuleOutcome::Undetermined`
+/// with a `Finding` explaining why. Once `cancel_subprocesses` is called,
+/// the `Rule`s that have yet to start are not evaluated, and also report
+/// `RuleOutcome::Undetermined`.
+///
+/// With `cache` set, the outcomes are remembered in the culture target
+/// directory, and the `RuleReport`s of `Rule`s whose `inputs` have not
+/// changed since are marked as `cached` rather than evaluated again.
+///
+/// # Errors
+///
+/// Returns an error if the `prerequisites` of the `rules` form a cycle, or
+/// if the supplied `reporter` fails, most commonly because it cannot write
+/// to its output.
+pub fn check_culture_with_options<P: AsRef<Path>, R: Reporter + ?Sized>(
+    cargo_manifest_file_path: P,
+    verbose: bool,
+    options: &CheckOptions,
+    reporter: &mut R,
+    rules: &[&Rule],
+) -> Result<CultureReport, CheckError> {
+    let time_limits = TimeLimits {
+        run_deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+        rule_timeout: options.rule_timeout,
+    };
+    let rules = order_by_prerequisites(rules)?;
+    reporter.run_started(&rules)?;
+    let metadata_option =
+        read_cargo_metadata(cargo_manifest_file_path.as_ref(), verbose, reporter)?;
+    let cache = if options.cache {
+        ResultCache::load(cargo_manifest_file_path.as_ref(), &metadata_option)
+    } else {
+        None
+    };
+    let settings = RunSettings {
+        time_limits,
+        cache: cache.as_ref(),
+    };
+    let report = if options.jobs > 1 {
+        evaluate_rules_concurrently(
+            cargo_manifest_file_path.as_ref(),
+            verbose,
+            &metadata_option,
+            options.jobs,
+            settings,
+            reporter,
+            &rules,
+        )?
+    } else {
+        evaluate_rules(
+            cargo_manifest_file_path.as_ref(),
+            verbose,
+            &metadata_option,
+            settings,
+            reporter,
+            &rules,
+        )?
+    };
+    if let Some(cache) = cache {
+        if let Err(e) = cache.save() {
+            if verbose {
+                reporter.diagnostic(&format!("culture result cache problem: {}", e))?;
+            }
+        }
+    }
+    reporter.run_finished(&report)?;
+    Ok(report)
+}
+
+/// What the evaluation of each `Rule` in a check is subject to
+#[derive(Clone, Copy, Debug, Default)]
+struct RunSettings<'a> {
+    time_limits: TimeLimits,
+    cache: Option<&'a ResultCache>,
+}
+
+/// The time limits of a check, from which each `Rule`'s deadline is derived
+#[derive(Clone, Copy, Debug, Default)]
+struct TimeLimits {
+    run_deadline: Option<Instant>,
+    rule_timeout: Option<Duration>,
+}
+
+impl TimeLimits {
+    /// The deadline of a `Rule` whose evaluation starts now
+    fn rule_deadline(&self) -> Option<Instant> {
+        let rule_deadline = self.rule_timeout.map(|timeout| Instant::now() + timeout);
+        match (self.run_deadline, rule_deadline) {
+            (Some(run), Some(rule)) => Some(run.min(rule)),
+            (run, rule) => run.or(rule),
+        }
+    }
+}
+
+fn read_cargo_metadata<P: AsRef<Path>, R: Reporter + ?Sized>(
+    cargo_manifest_file_path: P,
+    verbose: bool,
+    reporter: &mut R,
 ) -> Result<Option<CargoMetadata>, CheckError> {
     let manifest_path: PathBuf = cargo_manifest_file_path.as_ref().to_path_buf();
     let metadata_result = cargo_metadata::metadata(Some(manifest_path.as_ref()));
     match metadata_result {
         Ok(m) => Ok(Some(m)),
         Err(e) => {
-            if verbose && writeln!(print_output, "cargo metadata problem: {}", e).is_err() {
-                return Err(CheckError::PrintOutputFailure {
-                    topic: "cargo metadata",
-                });
+            if verbose {
+                reporter.diagnostic(&format!("cargo metadata problem: {}", e))?;
             }
             Ok(None)
         }
     }
 }
 
-fn evaluate_rules<P: AsRef<Path>, W: Write, M: Borrow<Option<CargoMetadata>>>(
+/// Reorder `rules` so that each follows its `prerequisites`, while otherwise
+/// preserving the supplied order.
+fn order_by_prerequisites<'r>(rules: &[&'r Rule]) -> Result<Vec<&'r Rule>, CheckError> {
+    let mut remaining: Vec<&'r Rule> = rules.to_vec();
+    let mut ordered: Vec<&'r Rule> = Vec::with_capacity(rules.len());
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|rule| {
+            rule.prerequisites().iter().all(|p| {
+                ordered.iter().any(|o| o.id() == *p) || !remaining.iter().any(|r| r.id() == *p)
+            })
+        });
+        match ready {
+            Some(position) => ordered.push(remaining.remove(position)),
+            None => {
+                return Err(CheckError::CyclicPrerequisites {
+                    ids: remaining.iter().map(|r| r.id().to_owned()).collect(),
+                })
+            }
+        }
+    }
+    Ok(ordered)
+}
+
+/// For each of the `rules`, the indices of its `prerequisites` within `rules`
+fn prerequisite_indices(rules: &[&Rule]) -> Vec<Vec<usize>> {
+    rules
+        .iter()
+        .map(|rule| {
+            rule.prerequisites()
+                .iter()
+                .filter_map(|p| rules.iter().position(|r| r.id() == *p))
+                .collect()
+        })
+        .collect()
+}
+
+/// The `Rule` whose not being upheld is the reason others were skipped
+#[derive(Clone, Debug)]
+struct RootCause {
+    index: usize,
+    outcome: RuleOutcome,
+}
+
+/// Whether an evaluated `Rule` was upheld, or else the root cause
+type Resolution = Result<(), RootCause>;
+
+/// The root cause of any of the `prerequisites` not being upheld, which
+/// must all be resolved already
+fn root_cause(prerequisites: &[usize], resolutions: &[Option<Resolution>]) -> Option<RootCause> {
+    prerequisites
+        .iter()
+        .filter_map(|&p| match resolutions[p] {
+            Some(Err(ref cause)) => Some(cause.clone()),
+            _ => None,
+        })
+        .next()
+}
+
+fn resolution(index: usize, rule_report: &RuleReport, root_cause: Option<RootCause>) -> Resolution {
+    match (root_cause, &rule_report.outcome) {
+        (Some(cause), _) => Err(cause),
+        (None, RuleOutcome::Success) => Ok(()),
+        (None, outcome) => Err(RootCause {
+            index,
+            outcome: outcome.clone(),
+        }),
+    }
+}
+
+fn evaluate_rules<P: AsRef<Path>, R: Reporter + ?Sized, M: Borrow<Option<CargoMetadata>>>(
     cargo_manifest_file_path: P,
     verbose: bool,
     metadata: M,
-    print_output: &mut W,
+    settings: RunSettings,
+    reporter: &mut R,
     rules: &[&Rule],
-) -> Result<OutcomesByDescription, CheckError> {
-    let mut outcomes = OutcomesByDescription::new();
-    for rule in rules {
-        let outcome = print_rule_evaluation(
-            *rule,
-            cargo_manifest_file_path.as_ref(),
-            verbose,
-            metadata.borrow(),
-            print_output,
-        );
-        outcomes.insert(rule.description().to_owned(), outcome?);
+) -> Result<CultureReport, CheckError> {
+    let mut report = CultureReport {
+        rules: Vec::with_capacity(rules.len()),
+    };
+    let prerequisites = prerequisite_indices(rules);
+    let mut resolutions: Vec<Option<Resolution>> = vec![None; rules.len()];
+    for (index, rule) in rules.iter().enumerate() {
+        reporter.rule_started(*rule)?;
+        let cause = root_cause(&prerequisites[index], &resolutions);
+        let rule_report = match cause {
+            Some(ref cause) => skipped_report(*rule, rules[cause.index], &cause.outcome),
+            None => evaluate_rule(
+                cargo_manifest_file_path.as_ref(),
+                verbose,
+                metadata.borrow(),
+                settings,
+                *rule,
+            ),
+        };
+        resolutions[index] = Some(resolution(index, &rule_report, cause));
+        reporter.rule_finished(*rule, &rule_report)?;
+        report.rules.push(rule_report);
     }
-    Ok(outcomes)
+    Ok(report)
 }
 
-fn print_outcome_stats<W: Write>(
-    outcomes: &OutcomesByDescription,
-    mut print_output: W,
-) -> Result<(), CheckError> {
-    let outcome_stats: OutcomeStats = outcomes.into();
-    let conclusion = if outcome_stats.is_success() {
-        "ok".green()
-    } else {
-        "FAILED".red()
+/// The `Rule`s that remain to be evaluated by `evaluate_rules_concurrently`,
+/// and the resolutions of those already evaluated
+struct Schedule {
+    pending: Vec<usize>,
+    resolutions: Vec<Option<Resolution>>,
+}
+
+/// Evaluate `rules` on up to `jobs` threads, as described for
+/// `check_culture_with_jobs`, notifying the `reporter` in order as soon as
+/// each leading `Rule`'s evaluation is complete.
+fn evaluate_rules_concurrently<R: Reporter + ?Sized>(
+    cargo_manifest_file_path: &Path,
+    verbose: bool,
+    metadata: &Option<CargoMetadata>,
+    jobs: usize,
+    settings: RunSettings,
+    reporter: &mut R,
+    rules: &[&Rule],
+) -> Result<CultureReport, CheckError> {
+    let prerequisites = prerequisite_indices(rules);
+    let (cheap, expensive): (Vec<usize>, Vec<usize>) =
+        (0..rules.len()).partition(|&i| rules[i].cost() == RuleCost::Cheap);
+    let schedule = Mutex::new(Schedule {
+        pending: cheap.into_iter().chain(expensive).collect(),
+        resolutions: vec![None; rules.len()],
+    });
+    let resolved = Condvar::new();
+    let expensive_lane = Mutex::new(());
+    let (sender, receiver) = mpsc::channel::<(usize, RuleReport)>();
+    let mut report = CultureReport {
+        rules: Vec::with_capacity(rules.len()),
     };
-    if writeln!(
-        print_output,
-        "culture result: {}. {} passed. {} failed. {} undetermined.",
-        conclusion,
-        outcome_stats.success_count,
-        outcome_stats.fail_count,
-        outcome_stats.undetermined_count
-    ).is_err()
-    {
-        return Err(CheckError::PrintOutputFailure {
-            topic: "culture check summary",
-        });
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(rules.len()) {
+            let sender = sender.clone();
+            let (schedule, resolved, expensive_lane) = (&schedule, &resolved, &expensive_lane);
+            let prerequisites = &prerequisites;
+            scope.spawn(move || {
+                let mut guard = schedule.lock().expect("Rule schedule poisoned");
+                loop {
+                    let ready = guard.pending.iter().position(|&i| {
+                        prerequisites[i]
+                            .iter()
+                            .all(|&p| guard.resolutions[p].is_some())
+                    });
+                    let index = match ready {
+                        Some(position) => guard.pending.remove(position),
+                        None if guard.pending.is_empty() => break,
+                        None => {
+                            guard = resolved.wait(guard).expect("Rule schedule poisoned");
+                            continue;
+                        }
+                    };
+                    let cause = root_cause(&prerequisites[index], &guard.resolutions);
+                    drop(guard);
+                    let rule = rules[index];
+                    let evaluation = panic::catch_unwind(AssertUnwindSafe(|| match cause {
+                        Some(ref cause) => skipped_report(rule, rules[cause.index], &cause.outcome),
+                        None => {
+                            let _lane = match rule.cost() {
+                                RuleCost::Expensive => Some(expensive_lane.lock()),
+                                RuleCost::Cheap => None,
+                            };
+                            evaluate_rule(
+                                cargo_manifest_file_path,
+                                verbose,
+                                metadata,
+                                settings,
+                                rule,
+                            )
+                        }
+                    }));
+                    guard = schedule.lock().expect("Rule schedule poisoned");
+                    let rule_report = match evaluation {
+                        Ok(rule_report) => rule_report,
+                        Err(panic) => {
+                            // Release the other workers rather than leave
+                            // them waiting on this rule forever
+                            guard.pending.clear();
+                            resolved.notify_all();
+                            drop(guard);
+                            panic::resume_unwind(panic);
+                        }
+                    };
+                    guard.resolutions[index] = Some(resolution(index, &rule_report, cause));
+                    resolved.notify_all();
+                    if sender.send((index, rule_report)).is_err() {
+                        guard.pending.clear();
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+        let mut completed: BTreeMap<usize, RuleReport> = BTreeMap::new();
+        for (index, rule_report) in receiver {
+            completed.insert(index, rule_report);
+            while let Some(rule_report) = completed.remove(&report.rules.len()) {
+                let rule = rules[report.rules.len()];
+                reporter.rule_started(rule)?;
+                reporter.rule_finished(rule, &rule_report)?;
+                report.rules.push(rule_report);
+            }
+        }
+        Ok(())
+    })?;
+    Ok(report)
+}
+
+fn evaluate_rule(
+    cargo_manifest_file_path: &Path,
+    verbose: bool,
+    metadata: &Option<CargoMetadata>,
+    settings: RunSettings,
+    rule: &Rule,
+) -> RuleReport {
+    if subprocesses_cancelled() {
+        return cancelled_report(rule);
+    }
+    let start = Instant::now();
+    let deadline = settings.time_limits.rule_deadline();
+    let fingerprint = settings
+        .cache
+        .and_then(|cache| cache.fingerprint(rule, verbose, deadline));
+    if let (Some(cache), Some(fingerprint)) = (settings.cache, fingerprint.as_ref()) {
+        if let Some(cached) = cache.lookup(rule, fingerprint, start.elapsed()) {
+            return cached;
+        }
+    }
+    let mut print_output: Vec<u8> = Vec::new();
+    let mut findings = Vec::new();
+    let outcome = rule.evaluate(
+        RuleContext::new(
+            cargo_manifest_file_path,
+            verbose,
+            metadata,
+            &mut print_output,
+            &mut findings,
+        ).with_deadline(deadline),
+    );
+    let report = RuleReport {
+        id: rule.id().to_owned(),
+        description: rule.description().to_owned(),
+        severity: rule.severity(),
+        outcome,
+        duration: start.elapsed(),
+        details: String::from_utf8_lossy(&print_output).into_owned(),
+        findings,
+        cached: false,
+    };
+    if let (Some(cache), Some(fingerprint)) = (settings.cache, fingerprint) {
+        cache.record(fingerprint, &report);
+    }
+    report
+}
+
+/// The `RuleReport` of a `rule` that was not evaluated because the
+/// prerequisite `root_cause` was not upheld, but had the `cause_outcome`
+fn skipped_report(rule: &Rule, root_cause: &Rule, cause_outcome: &RuleOutcome) -> RuleReport {
+    let cause_outcome = match *cause_outcome {
+        RuleOutcome::Failure => "failed",
+        RuleOutcome::Undetermined => "was undetermined",
+        RuleOutcome::Success | RuleOutcome::Skipped => "was not upheld",
     };
-    Ok(())
+    RuleReport {
+        id: rule.id().to_owned(),
+        description: rule.description().to_owned(),
+        severity: rule.severity(),
+        outcome: RuleOutcome::Skipped,
+        duration: Duration::from_secs(0),
+        details: String::new(),
+        findings: vec![Finding::new(format!(
+            "Skipped because the prerequisite rule {} {}: {}",
+            root_cause.id(),
+            cause_outcome,
+            root_cause.description()
+        ))],
+        cached: false,
+    }
+}
+
+/// The `RuleReport` of a `rule` that was not evaluated because
+/// `cancel_subprocesses` was called first
+fn cancelled_report(rule: &Rule) -> RuleReport {
+    RuleReport {
+        id: rule.id().to_owned(),
+        description: rule.description().to_owned(),
+        severity: rule.severity(),
+        outcome: RuleOutcome::Undetermined,
+        duration: Duration::from_secs(0),
+        details: String::new(),
+        findings: vec![Finding::new(
+            "Not evaluated because the check was cancelled",
+        )],
+        cached: false,
+    }
 }
 
-/// Map between the `description` of `Rule`s and the outcome of their execution
+/// Map between the `description` of `Rule`s and the outcome of their execution.
+///
+/// Retained for compatibility; a `CultureReport` can be converted into one.
 pub type OutcomesByDescription = HashMap<String, RuleOutcome>;
 
 /// Trait for summarizing whether the outcome of culture
@@ -361,8 +835,9 @@ impl IsSuccess for OutcomeStats {
         assert_eq!(0, self.undetermined_count,
                    "OutcomeStats::undetermined_count was {}, which counts as not a success",
                    self.undetermined_count);
-        assert!(self.success_count > 0,
-                   "OutcomeStats::success_count was {}, which counts as not a success",
+        assert!(self.success_count + self.warning_count + self.info_count > 0,
+                   "OutcomeStats::success_count was {} with no advisory rules evaluated, \
+                    which counts as not a success",
                    self.success_count);
     }
 }
@@ -378,6 +853,7 @@ where
                 RuleOutcome::Success => stats.success_count += 1,
                 RuleOutcome::Failure => stats.fail_count += 1,
                 RuleOutcome::Undetermined => stats.undetermined_count += 1,
+                RuleOutcome::Skipped => stats.skipped_count += 1,
             }
         }
         stats
@@ -393,47 +869,15 @@ where
     }
 }
 
-fn print_rule_evaluation<P: AsRef<Path>, W: Write, M: Borrow<Option<CargoMetadata>>>(
-    rule: &Rule,
-    cargo_manifest_file_path: P,
-    verbose: bool,
-    metadata: M,
-    print_output: &mut W,
-) -> Result<RuleOutcome, CheckError> {
-    if print_output
-        .write_all(rule.description().as_bytes())
-        .and_then(|_| print_output.flush())
-        .is_err()
-    {
-        return Err(CheckError::PrintOutputFailure {
-            topic: "rule description",
-        });
-    }
-    let outcome = rule.evaluate(RuleContext {
-        cargo_manifest_file_path: cargo_manifest_file_path.as_ref(),
-        verbose,
-        metadata: metadata.borrow(),
-        print_output,
-    });
-    if writeln!(print_output, " ... {}", summary_str(&outcome)).is_err() {
-        return Err(CheckError::PrintOutputFailure {
-            topic: "rule evaluation outcome",
-        });
-    }
-    Ok(outcome)
-}
-
-fn summary_str<T: Borrow<RuleOutcome>>(outcome: T) -> colored::ColoredString {
-    match *outcome.borrow() {
-        RuleOutcome::Success => "ok".green(),
-        RuleOutcome::Failure => "FAILED".red(),
-        RuleOutcome::Undetermined => "UNDETERMINED".red(),
-    }
-}
-
 /// Summary of result statistics generated from aggregating `RuleOutcome`s
 /// results for multiple Rule evaluations
-#[derive(Clone, Debug, Default, PartialEq)]
+///
+/// Only the `Rule`s of `Severity::Error` contribute to the `fail_count` and
+/// `undetermined_count`. The other outcomes of advisory `Rule`s are counted
+/// by `Severity` instead, and never make the aggregate a failure.
+/// `RuleOutcome::Skipped` instances are only counted in the `skipped_count`,
+/// since the prerequisite that caused them is counted already.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
 pub struct OutcomeStats {
     /// The number of `RuleOutcome::Success` instances observed
     pub success_count: usize,
@@ -441,12 +885,22 @@ pub struct OutcomeStats {
     pub fail_count: usize,
     /// The number of `RuleOutcome::Undetermined` instances observed
     pub undetermined_count: usize,
+    /// The number of `Severity::Warning` `Rule`s that were not upheld
+    pub warning_count: usize,
+    /// The number of `Severity::Info` `Rule`s that were not upheld
+    pub info_count: usize,
+    /// The number of `RuleOutcome::Skipped` instances observed
+    pub skipped_count: usize,
 }
 
 impl<'a> From<&'a OutcomeStats> for RuleOutcome {
     fn from(stats: &'a OutcomeStats) -> Self {
+        let evaluated_without_error = stats
+            .success_count
+            .saturating_add(stats.warning_count)
+            .saturating_add(stats.info_count);
         match (
-            stats.success_count,
+            evaluated_without_error,
             stats.fail_count,
             stats.undetermined_count,
         ) {
@@ -464,23 +918,31 @@ mod tests {
     use super::*;
     use proptest::collection::VecStrategy;
     use proptest::prelude::*;
+    use std::time::Duration;
 
     fn arb_rule_outcome() -> BoxedStrategy<RuleOutcome> {
         prop_oneof![
             Just(RuleOutcome::Success),
             Just(RuleOutcome::Undetermined),
             Just(RuleOutcome::Failure),
+            Just(RuleOutcome::Skipped),
         ].boxed()
     }
 
     prop_compose! {
         fn arb_stats()(success in any::<usize>(),
                        fail in any::<usize>(),
-                        undetermined in any::<usize>()) -> OutcomeStats {
+                        undetermined in any::<usize>(),
+                        warning in any::<usize>(),
+                        info in any::<usize>(),
+                        skipped in any::<usize>()) -> OutcomeStats {
             OutcomeStats {
                 success_count: success,
                 fail_count: fail,
-                undetermined_count: undetermined
+                undetermined_count: undetermined,
+                warning_count: warning,
+                info_count: info,
+                skipped_count: skipped
             }
         }
     }
@@ -529,10 +991,11 @@ mod tests {
         #[test]
         fn piles_of_fixed_outcome_rules_evaluable(ref verbose in any::<bool>(),
                                                   ref vec_of_rules in arb_vec_of_rules()) {
-            let mut v:Vec<u8> = Vec::new();
+            let mut reporter = TextReporter::new(Vec::new());
             let _outcome:OutcomeStats = evaluate_rules(
                                            Path::new("./Cargo.toml"), *verbose, &None,
-                                           &mut v,
+                                           RunSettings::default(),
+                                           &mut reporter,
                                            vec_of_rules.iter()
                                                .map(|r| r.as_ref())
                                                .collect::<Vec<&Rule>>()
@@ -541,6 +1004,169 @@ mod tests {
         }
     }
 
+    #[derive(Clone, Debug, PartialEq)]
+    struct SlowRule {
+        description: String,
+        cost: RuleCost,
+        delay: Duration,
+    }
+
+    impl Rule for SlowRule {
+        fn description(&self) -> &str {
+            &self.description
+        }
+
+        fn cost(&self) -> RuleCost {
+            self.cost
+        }
+
+        fn evaluate(&self, context: RuleContext) -> RuleOutcome {
+            thread::sleep(self.delay);
+            let _ = write!(context.print_output, "{}", self.description);
+            RuleOutcome::Success
+        }
+    }
+
+    #[test]
+    fn concurrent_evaluation_reports_in_declared_order() {
+        let rules: Vec<SlowRule> = (0..8u64)
+            .map(|i| SlowRule {
+                description: format!("Rule {}", i),
+                cost: if i % 3 == 0 {
+                    RuleCost::Expensive
+                } else {
+                    RuleCost::Cheap
+                },
+                delay: Duration::from_millis((8 - i) * 5),
+            })
+            .collect();
+        let rule_refs = rules.iter().map(|r| r as &Rule).collect::<Vec<&Rule>>();
+        let mut reporter = TextReporter::new(Vec::new());
+        let report = evaluate_rules_concurrently(
+            Path::new("./Cargo.toml"),
+            true,
+            &None,
+            4,
+            RunSettings::default(),
+            &mut reporter,
+            &rule_refs,
+        ).expect("Expect no trouble with eval");
+        assert_eq!(
+            rules.iter().map(|r| r.description.clone()).collect::<Vec<_>>(),
+            report.iter().map(|r| r.description.clone()).collect::<Vec<_>>()
+        );
+        for rule_report in &report {
+            assert_eq!(rule_report.description, rule_report.details);
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct PrerequisiteRule {
+        id: &'static str,
+        outcome: RuleOutcome,
+        prerequisites: Vec<&'static str>,
+    }
+
+    impl Rule for PrerequisiteRule {
+        fn description(&self) -> &str {
+            self.id
+        }
+
+        fn prerequisites(&self) -> Vec<&str> {
+            self.prerequisites.clone()
+        }
+
+        fn evaluate(&self, _context: RuleContext) -> RuleOutcome {
+            self.outcome.clone()
+        }
+    }
+
+    fn prerequisite_rule(
+        id: &'static str,
+        outcome: RuleOutcome,
+        prerequisites: Vec<&'static str>,
+    ) -> PrerequisiteRule {
+        PrerequisiteRule {
+            id,
+            outcome,
+            prerequisites,
+        }
+    }
+
+    #[test]
+    fn rules_whose_prerequisites_fail_are_skipped_after_them() {
+        let rules = [
+            prerequisite_rule("dependent", RuleOutcome::Success, vec!["indirect"]),
+            prerequisite_rule("indirect", RuleOutcome::Success, vec!["root"]),
+            prerequisite_rule("root", RuleOutcome::Failure, vec![]),
+            prerequisite_rule("unrelated", RuleOutcome::Success, vec!["absent"]),
+        ];
+        let rule_refs = rules.iter().map(|r| r as &Rule).collect::<Vec<&Rule>>();
+        let ordered = order_by_prerequisites(&rule_refs).expect("Should be ordered");
+        let path = Path::new("./Cargo.toml");
+        let sequential = evaluate_rules(
+            path,
+            false,
+            &None,
+            RunSettings::default(),
+            &mut TextReporter::new(Vec::new()),
+            &ordered,
+        );
+        let concurrent = evaluate_rules_concurrently(
+            path,
+            false,
+            &None,
+            3,
+            RunSettings::default(),
+            &mut TextReporter::new(Vec::new()),
+            &ordered,
+        );
+        for report in [sequential, concurrent] {
+            let report = report.expect("Expect no trouble with eval");
+            assert_eq!(
+                vec!["root", "indirect", "dependent", "unrelated"],
+                report.iter().map(|r| r.id.as_str()).collect::<Vec<_>>()
+            );
+            let skipped = report.get("dependent").expect("Should be reported");
+            assert_eq!(RuleOutcome::Skipped, skipped.outcome);
+            assert!(skipped.findings[0].message.contains("root failed"));
+            let stats = OutcomeStats::from(&report);
+            assert_eq!(1, stats.success_count);
+            assert_eq!(1, stats.fail_count);
+            assert_eq!(2, stats.skipped_count);
+        }
+    }
+
+    #[test]
+    fn cyclic_prerequisites_are_an_error() {
+        let rules = [
+            prerequisite_rule("a", RuleOutcome::Success, vec!["b"]),
+            prerequisite_rule("b", RuleOutcome::Success, vec!["a"]),
+        ];
+        let rule_refs = rules.iter().map(|r| r as &Rule).collect::<Vec<&Rule>>();
+        match order_by_prerequisites(&rule_refs) {
+            Err(CheckError::CyclicPrerequisites { ids }) => assert_eq!(vec!["a", "b"], ids),
+            other => panic!("Unexpected result {:?}", other.map(|r| r.len())),
+        }
+    }
+
+    #[test]
+    fn rule_deadline_is_the_earlier_of_the_time_limits() {
+        let run_deadline = Instant::now() + Duration::from_secs(60);
+        let short_rules = TimeLimits {
+            run_deadline: Some(run_deadline),
+            rule_timeout: Some(Duration::from_secs(1)),
+        };
+        let rule_deadline = short_rules.rule_deadline().expect("Should have a deadline");
+        assert!(rule_deadline < run_deadline);
+        let long_rules = TimeLimits {
+            rule_timeout: Some(Duration::from_secs(600)),
+            ..short_rules
+        };
+        assert_eq!(Some(run_deadline), long_rules.rule_deadline());
+        assert_eq!(None, TimeLimits::default().rule_deadline());
+    }
+
     #[allow(dead_code)]
     #[derive(Clone, Debug, Default, PartialEq)]
     struct IsProjectAtALuckyTime;
@@ -567,13 +1193,13 @@ mod tests {
 
     #[test]
     fn sanity_check_a_silly_rule_for_the_readme() {
-        let context = RuleContext {
-            cargo_manifest_file_path: &PathBuf::from("Cargo.toml"),
-            verbose: true,
-            metadata: &None,
-            print_output: &mut Vec::new(),
-        };
-        let _ = IsProjectAtALuckyTime::default().evaluate(context);
+        let _ = IsProjectAtALuckyTime::default().evaluate(RuleContext::new(
+            &PathBuf::from("Cargo.toml"),
+            true,
+            &None,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        ));
     }
 
     #[test]
@@ -598,17 +1224,30 @@ mod tests {
         OutcomeStats {
             success_count: 1,
             fail_count: 0,
-            undetermined_count: 0
+            undetermined_count: 0,
+            ..OutcomeStats::default()
         }.assert_success();
     }
 
+    #[test]
+    fn outcome_stats_advisory_outcomes_alone_succeed() {
+        let stats = OutcomeStats {
+            warning_count: 1,
+            info_count: 1,
+            ..OutcomeStats::default()
+        };
+        assert_eq!(RuleOutcome::Success, RuleOutcome::from(&stats));
+        stats.assert_success();
+    }
+
     #[test]
     #[should_panic]
     fn outcome_stats_assert_success_all_zero_failure() {
         OutcomeStats {
             success_count: 0,
             fail_count: 0,
-            undetermined_count: 0
+            undetermined_count: 0,
+            ..OutcomeStats::default()
         }.assert_success();
     }
 
@@ -618,7 +1257,8 @@ mod tests {
         OutcomeStats {
             success_count: 1,
             fail_count: 1,
-            undetermined_count: 0
+            undetermined_count: 0,
+            ..OutcomeStats::default()
         }.assert_success();
     }
 
@@ -628,7 +1268,8 @@ mod tests {
         OutcomeStats {
             success_count: 1,
             fail_count: 0,
-            undetermined_count: 1
+            undetermined_count: 1,
+            ..OutcomeStats::default()
         }.assert_success();
     }
 
@@ -638,7 +1279,8 @@ mod tests {
         OutcomeStats {
             success_count: 1,
             fail_count: 1,
-            undetermined_count: 1
+            undetermined_count: 1,
+            ..OutcomeStats::default()
         }.assert_success();
     }
 
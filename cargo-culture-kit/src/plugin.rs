@@ -0,0 +1,494 @@
+//! Support for `Rule`s implemented by external executables, which can be
+//! written in any language and shipped without rebuilding `cargo-culture`.
+//!
+//! An `ExternalRule` runs its executable once for each request, writing a
+//! single `PluginRequest` JSON document to the executable's stdin and
+//! reading a single JSON document from its stdout.
+//!
+//! A `describe` request asks for a `PluginDescription`:
+//!
+//! ```json
+//! {"command": "describe"}
+//! ```
+//!
+//! ```json
+//! {"id": "has-codeowners-file", "description": "Should have a CODEOWNERS file.",
+//!  "justification": "...", "remediation": "...", "cost": "cheap", "severity": "error",
+//!  "prerequisites": ["cargo-metadata-readable"]}
+//! ```
+//!
+//! Only the `id` and `description` are required.
+//!
+//! An `evaluate` request supplies the equivalent of a `RuleContext`, where
+//! the `metadata` is `null` when cargo metadata is unavailable, and asks for
+//! a `PluginEvaluation`:
+//!
+//! ```json
+//! {"command": "evaluate", "manifest_path": "/project/Cargo.toml", "verbose": false,
+//!  "metadata": {"packages": [], "workspace_members": [], "...": "..."}}
+//! ```
+//!
+//! ```json
+//! {"outcome": "failure", "details": "No CODEOWNERS file was found.",
+//!  "findings": [{"message": "Searched /project", "file": null, "line": null}]}
+//! ```
+//!
+//! Only the `outcome` is required, and it must be `success`, `failure` or
+//! `undetermined`; a plugin claiming to be `skipped` is treated as
+//! undetermined. The executable must exit successfully
+//! whatever the outcome; anything it writes to stderr is treated as
+//! verbose details.
+//!
+//! Executables whose names start with `PLUGIN_PREFIX` can be found with
+//! `find_plugins_on_path`.
+use super::process::{self, RunError};
+use super::rules::{Finding, Rule, RuleContext, RuleCost, RuleOutcome, Severity};
+use super::CargoMetadata;
+use serde::de::DeserializeOwned;
+use serde_json;
+use std::env;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// The prefix of the names of executables that `find_plugins_on_path`
+/// considers to be rule plugins, such as `cargo-culture-rule-codeowners`
+pub const PLUGIN_PREFIX: &str = "cargo-culture-rule-";
+
+/// How long a rule plugin has to describe itself when loaded from a
+/// culture configuration or found on the `PATH`
+pub const PLUGIN_DESCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors specific to communicating with rule plugin executables.
+#[derive(Debug, Clone, Eq, Fail, PartialEq, Hash)]
+pub enum PluginError {
+    #[fail(display = "Could not run the rule plugin {}: {}", program, cause)]
+    /// The plugin executable could not be started, or its output collected.
+    LaunchFailure {
+        /// The plugin executable
+        program: String,
+        /// A human-oriented description of the underlying problem
+        cause: String,
+    },
+    #[fail(display = "The rule plugin {} did not respond as expected: {}", program, message)]
+    /// The plugin exited unsuccessfully, or its output was not the expected
+    /// JSON document.
+    ProtocolViolation {
+        /// The plugin executable
+        program: String,
+        /// A human-oriented description of the problem
+        message: String,
+    },
+    /// Destructuring should not be exhaustive.
+    ///
+    /// This enum may grow additional variants, so this hidden variant
+    /// ensures users do not rely on exhaustive matching.
+    #[doc(hidden)]
+    #[fail(display = "A hidden variant to increase expansion flexibility")]
+    __Nonexhaustive,
+}
+
+/// A request written to the stdin of a rule plugin executable.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+pub enum PluginRequest {
+    /// Ask for the plugin's `PluginDescription`
+    Describe,
+    /// Ask for a `PluginEvaluation` of the project
+    Evaluate {
+        /// The path of the Cargo.toml file of the project under evaluation
+        manifest_path: PathBuf,
+        /// Whether additional human-oriented `details` are wanted
+        verbose: bool,
+        /// The cargo metadata of the project, if it could be read
+        metadata: Option<CargoMetadata>,
+    },
+}
+
+/// The response of a rule plugin to a `PluginRequest::Describe`, supplying
+/// the equivalents of the `Rule` trait's methods.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PluginDescription {
+    /// The `Rule::id`
+    pub id: String,
+    /// The `Rule::description`
+    pub description: String,
+    /// The `Rule::justification`, if any
+    #[serde(default)]
+    pub justification: Option<String>,
+    /// The `Rule::remediation`, if any
+    #[serde(default)]
+    pub remediation: Option<String>,
+    /// The `Rule::cost`, which is assumed to be `RuleCost::Expensive`
+    /// when absent
+    #[serde(default)]
+    pub cost: Option<RuleCost>,
+    /// The `Rule::severity`, which is `Severity::Error` when absent
+    #[serde(default)]
+    pub severity: Option<Severity>,
+    /// The `Rule::prerequisites`, if any
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    /// The `Rule::inputs`, if any
+    #[serde(default)]
+    pub inputs: Vec<PathBuf>,
+}
+
+/// The response of a rule plugin to a `PluginRequest::Evaluate`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PluginEvaluation {
+    /// Whether the `Rule` was upheld, which may not be
+    /// `RuleOutcome::Skipped`
+    pub outcome: RuleOutcome,
+    /// Human-oriented content, equivalent to that written to
+    /// `RuleContext::print_output`
+    #[serde(default)]
+    pub details: String,
+    /// Structured evidence for the `outcome`
+    #[serde(default)]
+    pub findings: Vec<Finding>,
+}
+
+/// A `Rule` evaluated by an external executable, following the protocol
+/// described in the `plugin` module documentation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalRule {
+    program: PathBuf,
+    description: PluginDescription,
+}
+
+impl ExternalRule {
+    /// Ask the executable at `program` to describe itself, and wrap it
+    /// as a `Rule`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PluginError` when the executable cannot be run or does
+    /// not describe itself as expected.
+    pub fn load<P: Into<PathBuf>>(program: P) -> Result<Self, PluginError> {
+        ExternalRule::load_before(program.into(), None)
+    }
+
+    /// Ask the executable at `program` to describe itself within the
+    /// `timeout`, and wrap it as a `Rule`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PluginError` when the executable cannot be run or does
+    /// not describe itself as expected before the `timeout` elapses.
+    pub fn load_with_timeout<P: Into<PathBuf>>(
+        program: P,
+        timeout: Duration,
+    ) -> Result<Self, PluginError> {
+        ExternalRule::load_before(program.into(), Some(Instant::now() + timeout))
+    }
+
+    fn load_before(program: PathBuf, deadline: Option<Instant>) -> Result<Self, PluginError> {
+        let (description, _) = exchange(&program, &PluginRequest::Describe, deadline)?;
+        Ok(ExternalRule {
+            program,
+            description,
+        })
+    }
+
+    /// The executable that evaluates this `Rule`
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+}
+
+impl Rule for ExternalRule {
+    fn description(&self) -> &str {
+        &self.description.description
+    }
+
+    fn id(&self) -> &str {
+        &self.description.id
+    }
+
+    fn justification(&self) -> Option<&str> {
+        self.description.justification.as_deref()
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        self.description.remediation.as_deref()
+    }
+
+    fn cost(&self) -> RuleCost {
+        self.description.cost.unwrap_or(RuleCost::Expensive)
+    }
+
+    fn severity(&self) -> Severity {
+        self.description.severity.unwrap_or_default()
+    }
+
+    fn prerequisites(&self) -> Vec<&str> {
+        self.description
+            .prerequisites
+            .iter()
+            .map(|p| p.as_str())
+            .collect()
+    }
+
+    fn inputs(&self) -> Vec<PathBuf> {
+        self.description.inputs.clone()
+    }
+
+    fn configuration(&self) -> String {
+        json!({ "program": self.program }).to_string()
+    }
+
+    fn evaluate(&self, context: RuleContext) -> RuleOutcome {
+        let request = PluginRequest::Evaluate {
+            manifest_path: context.cargo_manifest_file_path.to_path_buf(),
+            verbose: context.verbose,
+            metadata: context.metadata.clone(),
+        };
+        match exchange::<PluginEvaluation>(&self.program, &request, context.deadline) {
+            Ok((evaluation, stderr)) => {
+                let _ = context.print_output.write_all(evaluation.details.as_bytes());
+                if context.verbose {
+                    let _ = context.print_output.write_all(stderr.as_bytes());
+                }
+                context.findings.extend(evaluation.findings);
+                match evaluation.outcome {
+                    RuleOutcome::Skipped => {
+                        // Only a check may skip a rule, when its
+                        // prerequisites are not upheld
+                        context.findings.push(Finding::new(format!(
+                            "The rule plugin {} claimed to be skipped, which is not an \
+                             outcome a plugin may report",
+                            self.program.display()
+                        )));
+                        RuleOutcome::Undetermined
+                    }
+                    outcome => outcome,
+                }
+            }
+            Err(e) => {
+                context.findings.push(Finding::new(e.to_string()));
+                RuleOutcome::Undetermined
+            }
+        }
+    }
+}
+
+/// Send `request` to the executable at `program`, returning its parsed
+/// response along with anything it wrote to stderr, unless the `deadline`
+/// passes first.
+fn exchange<T: DeserializeOwned>(
+    program: &Path,
+    request: &PluginRequest,
+    deadline: Option<Instant>,
+) -> Result<(T, String), PluginError> {
+    let program_name = program.display().to_string();
+    let protocol_violation = |message: String| PluginError::ProtocolViolation {
+        program: program_name.clone(),
+        message,
+    };
+    let input = serde_json::to_vec(request).map_err(|e| protocol_violation(e.to_string()))?;
+    let output = process::run(&mut Command::new(program), Some(&input), deadline).map_err(
+        |e| match e {
+            RunError::Launch(cause) => PluginError::LaunchFailure {
+                program: program_name.clone(),
+                cause: cause.to_string(),
+            },
+            e => protocol_violation(e.to_string()),
+        },
+    )?;
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    if !output.status.success() {
+        return Err(protocol_violation(format!(
+            "exited with {}: {}",
+            output.status,
+            stderr.trim()
+        )));
+    }
+    let response = serde_json::from_slice(&output.stdout)
+        .map_err(|e| protocol_violation(format!("unexpected output: {}", e)))?;
+    Ok((response, stderr))
+}
+
+/// Find the rule plugin executables, whose names start with `PLUGIN_PREFIX`,
+/// in the directories of the `PATH` environment variable.
+///
+/// When several directories contain a plugin of the same name, only the
+/// first is used, just as a shell would.
+pub fn find_plugins_on_path() -> Vec<PathBuf> {
+    match env::var_os("PATH") {
+        Some(path) => find_plugins_in_dirs(env::split_paths(&path)),
+        None => Vec::new(),
+    }
+}
+
+/// Find the rule plugin executables, whose names start with `PLUGIN_PREFIX`,
+/// directly within the supplied `dirs`.
+pub fn find_plugins_in_dirs<I: IntoIterator<Item = PathBuf>>(dirs: I) -> Vec<PathBuf> {
+    let mut plugins: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        let entries = match read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        let mut found: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| name.starts_with(PLUGIN_PREFIX))
+                    .unwrap_or(false) && is_executable(path)
+            })
+            .filter(|path| {
+                !plugins
+                    .iter()
+                    .any(|p| p.file_name() == path.file_name())
+            })
+            .collect();
+        found.sort();
+        plugins.extend(found);
+    }
+    plugins
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs::{set_permissions, File};
+    use std::io::Write;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    const PLUGIN_SCRIPT: &str = r#"#!/bin/sh
+request=$(cat)
+case "$request" in
+  *'"command":"describe"'*)
+    echo '{"id": "has-codeowners-file", "description": "Should have a CODEOWNERS file."}' ;;
+  *'"verbose":true'*)
+    echo 'Looked everywhere.' >&2
+    echo '{"outcome": "failure", "details": "No CODEOWNERS.", "findings": [{"message": "x"}]}' ;;
+  *)
+    echo '{"outcome": "success"}' ;;
+esac
+"#;
+
+    fn write_plugin(dir: &Path, name: &str, script: &str) -> PathBuf {
+        let path = dir.join(name);
+        {
+            let mut file = File::create(&path).expect("Could not create plugin");
+            file.write_all(script.as_bytes())
+                .and_then(|_| file.sync_all())
+                .expect("Could not write plugin");
+        }
+        set_permissions(&path, PermissionsExt::from_mode(0o755))
+            .expect("Could not make plugin executable");
+        path
+    }
+
+    fn evaluate(rule: &Rule, verbose: bool) -> (RuleOutcome, String, Vec<Finding>) {
+        let mut print_output: Vec<u8> = Vec::new();
+        let mut findings = Vec::new();
+        let outcome = rule.evaluate(RuleContext {
+            cargo_manifest_file_path: Path::new("Cargo.toml"),
+            verbose,
+            metadata: &None,
+            deadline: None,
+            print_output: &mut print_output,
+            findings: &mut findings,
+        });
+        (
+            outcome,
+            String::from_utf8(print_output).expect("Output should be utf8"),
+            findings,
+        )
+    }
+
+    #[test]
+    fn external_rule_describes_and_evaluates_through_the_plugin() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let program = write_plugin(dir.path(), "cargo-culture-rule-codeowners", PLUGIN_SCRIPT);
+        let rule = ExternalRule::load(&program).expect("Should load");
+        assert_eq!("has-codeowners-file", rule.id());
+        assert_eq!("Should have a CODEOWNERS file.", rule.description());
+        assert_eq!(RuleCost::Expensive, rule.cost());
+        assert_eq!(Severity::Error, rule.severity());
+
+        assert_eq!(RuleOutcome::Success, evaluate(&rule, false).0);
+        let (outcome, details, findings) = evaluate(&rule, true);
+        assert_eq!(RuleOutcome::Failure, outcome);
+        assert_eq!("No CODEOWNERS.Looked everywhere.\n", details);
+        assert_eq!(vec![Finding::new("x")], findings);
+    }
+
+    #[test]
+    fn external_rule_misbehavior_is_undetermined() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let program = write_plugin(dir.path(), "describes-only", PLUGIN_SCRIPT);
+        let rule = ExternalRule::load(&program).expect("Should load");
+        write_plugin(dir.path(), "describes-only", "#!/bin/sh\necho 'Oops' >&2\nexit 3\n");
+        let (outcome, _, findings) = evaluate(&rule, false);
+        assert_eq!(RuleOutcome::Undetermined, outcome);
+        assert!(findings[0].message.contains("Oops"));
+
+        match ExternalRule::load(dir.path().join("absent")) {
+            Err(PluginError::LaunchFailure { .. }) => (),
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn external_rule_claiming_to_be_skipped_is_undetermined() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let program = write_plugin(dir.path(), "skips", PLUGIN_SCRIPT);
+        let rule = ExternalRule::load(&program).expect("Should load");
+        write_plugin(
+            dir.path(),
+            "skips",
+            "#!/bin/sh\necho '{\"outcome\": \"skipped\", \"findings\": [{\"message\": \"x\"}]}'\n",
+        );
+        let (outcome, _, findings) = evaluate(&rule, false);
+        assert_eq!(RuleOutcome::Undetermined, outcome);
+        assert_eq!(Finding::new("x"), findings[0]);
+        assert!(findings[1].message.contains("claimed to be skipped"));
+    }
+
+    #[test]
+    fn external_rule_that_will_not_describe_itself_in_time_fails_to_load() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let program = write_plugin(dir.path(), "hangs", "#!/bin/sh\nsleep 30\n");
+        let start = Instant::now();
+        match ExternalRule::load_with_timeout(program, Duration::from_millis(200)) {
+            Err(PluginError::ProtocolViolation { .. }) => (),
+            other => panic!("Unexpected result {:?}", other),
+        }
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn find_plugins_in_dirs_prefers_earlier_dirs() {
+        let first = tempdir().expect("Failed to make a temp dir");
+        let second = tempdir().expect("Failed to make a temp dir");
+        let winner = write_plugin(first.path(), "cargo-culture-rule-a", "");
+        write_plugin(second.path(), "cargo-culture-rule-a", "");
+        let other = write_plugin(second.path(), "cargo-culture-rule-b", "");
+        write_plugin(second.path(), "cargo-something-else", "");
+        File::create(second.path().join("cargo-culture-rule-c")).expect("Could not create");
+        assert_eq!(
+            vec![winner, other],
+            find_plugins_in_dirs(vec![first.path().to_path_buf(), second.path().to_path_buf()])
+        );
+    }
+}
@@ -0,0 +1,218 @@
+//! Running the subprocesses of `Rule`s, such as `cargo build`, so that they
+//! can be abandoned when a deadline passes or the check is cancelled.
+//!
+//! Each subprocess is started in a process group of its own, so that the
+//! whole tree of processes it starts, such as `rustc` or test binaries,
+//! can be killed along with it.
+use std::fmt::{self, Display};
+use std::io::{self, Read, Write};
+#[cfg(unix)]
+use std::mem;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// How often a running subprocess is checked for completion, cancellation,
+/// and its deadline
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Request that every subprocess started on behalf of a `Rule` is killed,
+/// along with any processes it started, and that no more are started.
+///
+/// The `Rule`s that are interrupted report `RuleOutcome::Undetermined`.
+/// Intended to be called from an interrupt handler, such as for Ctrl-C,
+/// since the subprocesses do not share the terminal's process group and so
+/// do not receive its interrupts.
+pub fn cancel_subprocesses() {
+    CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// Whether `cancel_subprocesses` has been called
+pub fn subprocesses_cancelled() -> bool {
+    CANCELLED.load(Ordering::SeqCst)
+}
+
+/// The reasons a subprocess did not run to completion.
+#[derive(Debug)]
+pub(crate) enum RunError {
+    /// The subprocess could not be started, or waited upon
+    Launch(io::Error),
+    /// The subprocess was killed because its deadline passed
+    TimedOut,
+    /// The subprocess was killed, or never started, because
+    /// `cancel_subprocesses` was called
+    Cancelled,
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RunError::Launch(ref e) => write!(f, "could not be run: {}", e),
+            RunError::TimedOut => write!(f, "was killed because it ran out of time"),
+            RunError::Cancelled => write!(f, "was cancelled"),
+        }
+    }
+}
+
+/// Run `command` to completion, supplying it with `input` and collecting
+/// its output, unless the `deadline` passes or `cancel_subprocesses` is
+/// called first, in which case its process group is killed. Any processes
+/// it leaves running in its process group when it exits are killed as well.
+pub(crate) fn run(
+    command: &mut Command,
+    input: Option<&[u8]>,
+    deadline: Option<Instant>,
+) -> Result<Output, RunError> {
+    if subprocesses_cancelled() {
+        return Err(RunError::Cancelled);
+    }
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    isolate_process_group(command);
+    let mut child = command.spawn().map_err(RunError::Launch)?;
+    if let (Some(mut stdin), Some(input)) = (child.stdin.take(), input) {
+        let input = input.to_vec();
+        // A subprocess that needs nothing from its input may exit without
+        // reading it, so a broken pipe is not a problem in itself
+        thread::spawn(move || stdin.write_all(&input));
+    }
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let status = wait(&mut child, deadline)?;
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn wait(child: &mut Child, deadline: Option<Instant>) -> Result<ExitStatus, RunError> {
+    loop {
+        if has_exited(child)? {
+            // Processes the subprocess left behind, such as one started in
+            // the background, would otherwise keep its output pipes open
+            kill_process_group(child);
+            return child.wait().map_err(RunError::Launch);
+        }
+        let problem = if subprocesses_cancelled() {
+            RunError::Cancelled
+        } else if deadline.is_some_and(|d| Instant::now() >= d) {
+            RunError::TimedOut
+        } else {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        };
+        kill_process_group(child);
+        let _ = child.wait();
+        return Err(problem);
+    }
+}
+
+fn read_in_background<R: Read + Send + 'static>(source: Option<R>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut content = Vec::new();
+        if let Some(mut source) = source {
+            let _ = source.read_to_end(&mut content);
+        }
+        content
+    })
+}
+
+/// Whether `child` has exited. On unix, an exited `child` is left unreaped,
+/// so that its process group id stays its own until `child` is waited upon.
+#[cfg(unix)]
+fn has_exited(child: &mut Child) -> Result<bool, RunError> {
+    let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+    let result = unsafe {
+        libc::waitid(
+            libc::P_PID,
+            child.id() as libc::id_t,
+            &mut info,
+            libc::WEXITED | libc::WNOHANG | libc::WNOWAIT,
+        )
+    };
+    if result == -1 {
+        return Err(RunError::Launch(io::Error::last_os_error()));
+    }
+    // With `WNOHANG`, a child that is still running leaves `info` zeroed
+    Ok(unsafe { info.si_pid() } != 0)
+}
+
+#[cfg(not(unix))]
+fn has_exited(child: &mut Child) -> Result<bool, RunError> {
+    child
+        .try_wait()
+        .map(|status| status.is_some())
+        .map_err(RunError::Launch)
+}
+
+#[cfg(unix)]
+fn isolate_process_group(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn isolate_process_group(_command: &mut Command) {}
+
+#[cfg(unix)]
+fn kill_process_group(child: &mut Child) {
+    // The child leads its own process group, whose id is the child's id
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_collects_output_and_supplies_input() {
+        let output = run(&mut Command::new("cat"), Some(b"meow"), None).expect("Should run");
+        assert!(output.status.success());
+        assert_eq!(b"meow".to_vec(), output.stdout);
+    }
+
+    #[test]
+    fn run_does_not_wait_on_processes_left_behind() {
+        let start = Instant::now();
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("sleep 30 & echo started");
+        let output = run(&mut command, None, None).expect("Should run");
+        assert!(output.status.success());
+        assert_eq!(b"started\n".to_vec(), output.stdout);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn run_kills_the_process_tree_after_the_deadline() {
+        let start = Instant::now();
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("sleep 30 & sleep 30; echo done");
+        let result = run(
+            &mut command,
+            None,
+            Some(Instant::now() + Duration::from_millis(200)),
+        );
+        match result {
+            Err(RunError::TimedOut) => (),
+            other => panic!("Unexpected result {:?}", other),
+        }
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,227 @@
+//! Provides `CultureReport`, the ordered and timed result of a
+//! `check_culture` run.
+use super::{Finding, IsSuccess, OutcomeStats, OutcomesByDescription, RuleOutcome, Severity};
+use std::slice::Iter;
+use std::time::Duration;
+
+/// The complete result of a `check_culture` run, with an entry for each
+/// `Rule` in the order the `Rule`s were evaluated.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CultureReport {
+    /// The per-`Rule` results, in evaluation order
+    pub rules: Vec<RuleReport>,
+}
+
+/// The result of evaluating a single `Rule` as part of a `CultureReport`
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleReport {
+    /// The `id` of the evaluated `Rule`
+    pub id: String,
+    /// The `description` of the evaluated `Rule`
+    pub description: String,
+    /// The `severity` of the evaluated `Rule`
+    pub severity: Severity,
+    /// The outcome of the `Rule`'s evaluation
+    pub outcome: RuleOutcome,
+    /// Wall-clock time spent evaluating the `Rule`
+    pub duration: Duration,
+    /// Any textual content the `Rule` wrote to its `print_output`.
+    /// Typically empty unless `verbose` was requested.
+    pub details: String,
+    /// Structured evidence the `Rule` supplied to explain its outcome
+    pub findings: Vec<Finding>,
+    /// True when the outcome was reused from an earlier check, because the
+    /// `Rule`'s inputs were unchanged, rather than freshly evaluated
+    pub cached: bool,
+}
+
+impl CultureReport {
+    /// The number of `Rule`s evaluated
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// True when no `Rule`s were evaluated
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Iterate over the per-`Rule` results in evaluation order
+    pub fn iter(&self) -> Iter<'_, RuleReport> {
+        self.rules.iter()
+    }
+
+    /// Look up the result for the `Rule` with the given `id` or `description`
+    pub fn get(&self, id_or_description: &str) -> Option<&RuleReport> {
+        self.rules
+            .iter()
+            .find(|r| r.id == id_or_description || r.description == id_or_description)
+    }
+
+    /// The sum of the wall-clock time spent evaluating each `Rule`
+    pub fn total_duration(&self) -> Duration {
+        self.rules
+            .iter()
+            .fold(Duration::from_secs(0), |total, r| total + r.duration)
+    }
+}
+
+impl<'a> IntoIterator for &'a CultureReport {
+    type Item = &'a RuleReport;
+    type IntoIter = Iter<'a, RuleReport>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rules.iter()
+    }
+}
+
+impl IsSuccess for CultureReport {
+    fn is_success(&self) -> bool {
+        OutcomeStats::from(self).is_success()
+    }
+
+    fn assert_success(&self) {
+        assert!(!self.is_empty(), "CultureReport::len() should be > 0 to count as a success");
+        for rule in self.iter().filter(|r| r.severity == Severity::Error) {
+            assert_eq!(RuleOutcome::Success, rule.outcome,
+                       "The rule \"{}\" was not a success, but instead was {:?}",
+                       rule.description, rule.outcome)
+        }
+    }
+}
+
+impl<'a> From<&'a CultureReport> for OutcomeStats {
+    fn from(report: &'a CultureReport) -> Self {
+        let mut stats = OutcomeStats::default();
+        for rule in report {
+            match (&rule.outcome, rule.severity) {
+                (RuleOutcome::Success, _) => stats.success_count += 1,
+                (RuleOutcome::Skipped, _) => stats.skipped_count += 1,
+                (_, Severity::Warning) => stats.warning_count += 1,
+                (_, Severity::Info) => stats.info_count += 1,
+                (RuleOutcome::Failure, Severity::Error) => stats.fail_count += 1,
+                (RuleOutcome::Undetermined, Severity::Error) => stats.undetermined_count += 1,
+            }
+        }
+        stats
+    }
+}
+
+impl From<CultureReport> for OutcomeStats {
+    fn from(report: CultureReport) -> Self {
+        OutcomeStats::from(&report)
+    }
+}
+
+impl<'a> From<&'a CultureReport> for RuleOutcome {
+    fn from(report: &'a CultureReport) -> Self {
+        RuleOutcome::from(&OutcomeStats::from(report))
+    }
+}
+
+impl From<CultureReport> for RuleOutcome {
+    fn from(report: CultureReport) -> Self {
+        RuleOutcome::from(&report)
+    }
+}
+
+impl<'a> From<&'a CultureReport> for OutcomesByDescription {
+    fn from(report: &'a CultureReport) -> Self {
+        report
+            .iter()
+            .map(|r| (r.description.clone(), r.outcome.clone()))
+            .collect()
+    }
+}
+
+impl From<CultureReport> for OutcomesByDescription {
+    fn from(report: CultureReport) -> Self {
+        report
+            .rules
+            .into_iter()
+            .map(|r| (r.description, r.outcome))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::reporter::test_support::{rule_report, FixedRule};
+    use super::*;
+
+    fn timed_report(description: &'static str, outcome: RuleOutcome, millis: u64) -> RuleReport {
+        RuleReport {
+            id: description.to_lowercase(),
+            duration: Duration::from_millis(millis),
+            ..rule_report(&FixedRule(description, outcome.clone()), outcome)
+        }
+    }
+
+    #[test]
+    fn culture_report_conversions_agree_with_outcomes_by_description() {
+        let report = CultureReport {
+            rules: vec![
+                timed_report("B", RuleOutcome::Success, 5),
+                timed_report("A", RuleOutcome::Undetermined, 7),
+            ],
+        };
+        let outcomes = OutcomesByDescription::from(&report);
+        assert_eq!(OutcomeStats::from(&outcomes), OutcomeStats::from(&report));
+        assert_eq!(RuleOutcome::Undetermined, RuleOutcome::from(&report));
+        assert_eq!(Duration::from_millis(12), report.total_duration());
+        assert_eq!(
+            vec!["B", "A"],
+            report.iter().map(|r| r.description.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&RuleOutcome::Success), report.get("B").map(|r| &r.outcome));
+        assert_eq!(Some(&RuleOutcome::Undetermined), report.get("a").map(|r| &r.outcome));
+    }
+
+    #[test]
+    fn culture_report_stats_count_advisory_rules_separately() {
+        let advisory = |description, severity, outcome| RuleReport {
+            severity,
+            ..timed_report(description, outcome, 0)
+        };
+        let report = CultureReport {
+            rules: vec![
+                timed_report("A", RuleOutcome::Success, 0),
+                advisory("B", Severity::Warning, RuleOutcome::Failure),
+                advisory("C", Severity::Info, RuleOutcome::Undetermined),
+                advisory("D", Severity::Warning, RuleOutcome::Success),
+            ],
+        };
+        let stats = OutcomeStats::from(&report);
+        assert_eq!(2, stats.success_count);
+        assert_eq!(0, stats.fail_count);
+        assert_eq!(0, stats.undetermined_count);
+        assert_eq!(1, stats.warning_count);
+        assert_eq!(1, stats.info_count);
+        assert!(report.is_success());
+        report.assert_success();
+    }
+
+    #[test]
+    fn culture_report_assert_success_minimal_success() {
+        CultureReport {
+            rules: vec![timed_report("A", RuleOutcome::Success, 0)],
+        }.assert_success();
+    }
+
+    #[test]
+    #[should_panic]
+    fn culture_report_assert_success_empty_failure() {
+        CultureReport::default().assert_success();
+    }
+
+    #[test]
+    #[should_panic]
+    fn culture_report_assert_success_any_failure_failure() {
+        CultureReport {
+            rules: vec![
+                timed_report("A", RuleOutcome::Success, 0),
+                timed_report("B", RuleOutcome::Failure, 0),
+            ],
+        }.assert_success();
+    }
+}
@@ -0,0 +1,209 @@
+//! Standalone HTML reporting of `Rule` evaluation results.
+//!
+//! The page embeds its own styling and references no external resources,
+//! so it can be archived or attached as a single file.
+use super::super::{
+    CheckError, CultureReport, IsSuccess, OutcomeStats, Rule, RuleOutcome, RuleReport,
+};
+use super::{advisory_summary, escape_xml, outcome_label, DocumentRow, Reporter};
+use std::io::Write;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 0.4em 0.6em; text-align: left; vertical-align: top; }
+th { background: #f3f3f3; }
+pre { margin: 0; white-space: pre-wrap; }
+.success { color: #1a7f37; }
+.failure { color: #cf222e; font-weight: bold; }
+.undetermined { color: #9a6700; font-weight: bold; }
+.advisory { color: #9a6700; }
+.skipped { color: #57606a; }";
+
+/// `Reporter` that collects the results of a run and writes them as a
+/// standalone HTML page once the run is finished.
+///
+/// The page contains a summary of the `OutcomeStats` followed by a table
+/// of every `Rule` with its outcome, justification, any `Finding`s and any
+/// verbose details.
+#[derive(Debug)]
+pub struct HtmlReporter<W: Write> {
+    output: W,
+    diagnostics: Vec<String>,
+    rows: Vec<DocumentRow>,
+}
+
+impl<W: Write> HtmlReporter<W> {
+    /// Create an `HtmlReporter` that writes its page to the supplied
+    /// `Write` instance.
+    pub fn new(output: W) -> Self {
+        HtmlReporter {
+            output,
+            diagnostics: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Unwrap this `HtmlReporter`, returning the underlying `Write` instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_document(&mut self, stats: &OutcomeStats) -> ::std::io::Result<()> {
+        let conclusion = if stats.is_success() {
+            ("success", "ok")
+        } else {
+            ("failure", "FAILED")
+        };
+        writeln!(self.output, "<!DOCTYPE html>")?;
+        writeln!(self.output, "<html lang=\"en\">")?;
+        writeln!(self.output, "<head>")?;
+        writeln!(self.output, "<meta charset=\"utf-8\">")?;
+        writeln!(self.output, "<title>Culture report</title>")?;
+        writeln!(self.output, "<style>\n{}\n</style>", STYLE)?;
+        writeln!(self.output, "</head>")?;
+        writeln!(self.output, "<body>")?;
+        writeln!(self.output, "<h1>Culture report</h1>")?;
+        writeln!(
+            self.output,
+            "<p>culture result: <span class=\"{}\">{}</span>. \
+             {} passed. {} failed. {} undetermined.{}</p>",
+            conclusion.0,
+            conclusion.1,
+            stats.success_count,
+            stats.fail_count,
+            stats.undetermined_count,
+            advisory_summary(stats)
+        )?;
+        writeln!(self.output, "<table>")?;
+        writeln!(
+            self.output,
+            "<tr><th>Rule</th><th>Outcome</th><th>Justification</th><th>Details</th></tr>"
+        )?;
+        for row in &self.rows {
+            let mut details = String::new();
+            if !row.findings.is_empty() {
+                details.push_str("<ul>");
+                for finding in &row.findings {
+                    details.push_str(&format!("<li>{}</li>", escape_xml(finding)));
+                }
+                details.push_str("</ul>");
+            }
+            if !row.details.is_empty() {
+                details.push_str(&format!("<pre>{}</pre>", escape_xml(&row.details)));
+            }
+            let outcome = match row.advisory_severity {
+                Some(severity) => format!("{} ({})", outcome_label(&row.outcome), severity),
+                None => outcome_label(&row.outcome).to_owned(),
+            };
+            writeln!(
+                self.output,
+                "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td><td>{}</td></tr>",
+                escape_xml(&row.description),
+                outcome_class(row),
+                outcome,
+                escape_xml(row.justification.as_ref().map_or("", |j| j.as_str())),
+                details
+            )?;
+        }
+        writeln!(self.output, "</table>")?;
+        if !self.diagnostics.is_empty() {
+            writeln!(self.output, "<h2>Diagnostics</h2>")?;
+            writeln!(self.output, "<ul>")?;
+            for diagnostic in &self.diagnostics {
+                writeln!(self.output, "<li>{}</li>", escape_xml(diagnostic))?;
+            }
+            writeln!(self.output, "</ul>")?;
+        }
+        writeln!(self.output, "</body>")?;
+        writeln!(self.output, "</html>")?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Reporter for HtmlReporter<W> {
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError> {
+        self.diagnostics.clear();
+        self.rows = Vec::with_capacity(rules.len());
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, message: &str) -> Result<(), CheckError> {
+        self.diagnostics.push(message.trim().to_owned());
+        Ok(())
+    }
+
+    fn rule_started(&mut self, _rule: &Rule) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        self.rows.push(DocumentRow::new(rule, report));
+        Ok(())
+    }
+
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError> {
+        let stats = OutcomeStats::from(report);
+        if self.write_document(&stats).is_err() {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "HTML report",
+            });
+        }
+        Ok(())
+    }
+}
+
+fn outcome_class(row: &DocumentRow) -> &'static str {
+    match (&row.outcome, row.advisory_severity) {
+        (RuleOutcome::Success, _) => "success",
+        (RuleOutcome::Skipped, _) => "skipped",
+        (_, Some(_)) => "advisory",
+        (RuleOutcome::Failure, None) => "failure",
+        (RuleOutcome::Undetermined, None) => "undetermined",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use Finding;
+
+    fn report(rules_and_details: &[(&FixedRule, &str)]) -> String {
+        let mut reporter = HtmlReporter::new(Vec::new());
+        simulate_run(&mut reporter, rules_and_details);
+        String::from_utf8(reporter.into_inner()).expect("Output should be utf8")
+    }
+
+    #[test]
+    fn html_report_is_a_standalone_page_with_row_per_rule() {
+        let pass = FixedRule("Should pass.", RuleOutcome::Success);
+        let unknown = FixedRule("Should be unknown.", RuleOutcome::Undetermined);
+        let html = report(&[(&pass, ""), (&unknown, "")]);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(!html.contains("http"));
+        assert!(html.contains("1 passed. 0 failed. 1 undetermined."));
+        assert!(html.contains("<td>Should pass.</td><td class=\"success\">ok</td>"));
+        assert!(html.contains("<td class=\"undetermined\">UNDETERMINED</td>"));
+    }
+
+    #[test]
+    fn html_report_lists_findings() {
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "");
+        report.findings.push(Finding::new("<none> found"));
+        let mut reporter = HtmlReporter::new(Vec::new());
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let html = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(html.contains("<td><ul><li>&lt;none&gt; found</li></ul></td>"));
+    }
+
+    #[test]
+    fn html_report_escapes_details() {
+        let fail = FixedRule("Should <fail>.", RuleOutcome::Failure);
+        let html = report(&[(&fail, "warning: <unused> & \"odd\"\n")]);
+        assert!(html.contains("<td>Should &lt;fail&gt;.</td>"));
+        assert!(html.contains("<pre>warning: &lt;unused&gt; &amp; &quot;odd&quot;</pre>"));
+    }
+}
@@ -0,0 +1,332 @@
+//! Machine-readable JSON reporting of `Rule` evaluation results.
+//!
+//! Intended for consumption by CI dashboards and other tooling that would
+//! otherwise have to scrape the human-oriented textual output.
+use super::super::{
+    check_culture, CheckError, CultureReport, Finding, OutcomeStats, Rule, RuleOutcome, RuleReport,
+    Severity,
+};
+use super::Reporter;
+use serde_json;
+use std::io::Write;
+use std::path::Path;
+
+/// The complete JSON document produced by a `JsonReporter`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JsonReport {
+    /// The aggregate outcome of all `Rule`s evaluated
+    pub outcome: RuleOutcome,
+    /// The per-`Rule` results, in the order the `Rule`s were evaluated
+    pub rules: Vec<JsonRuleEvaluation>,
+    /// Totals for each kind of `RuleOutcome` observed
+    pub stats: OutcomeStats,
+}
+
+/// The result of evaluating a single `Rule`, as represented in a `JsonReport`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JsonRuleEvaluation {
+    /// The `id` of the evaluated `Rule`
+    pub id: String,
+    /// The `description` of the evaluated `Rule`
+    pub description: String,
+    /// The `severity` of the evaluated `Rule`
+    pub severity: Severity,
+    /// The outcome of the `Rule`'s evaluation
+    pub outcome: RuleOutcome,
+    /// Wall-clock time spent evaluating the `Rule`, in seconds
+    pub duration_secs: f64,
+    /// Any textual content the `Rule` wrote to its `print_output`.
+    /// Typically empty unless `verbose` was requested.
+    pub details: String,
+    /// Structured evidence the `Rule` supplied to explain its outcome
+    pub findings: Vec<Finding>,
+    /// True when the outcome was reused from an earlier check
+    pub cached: bool,
+}
+
+/// `Reporter` that collects the results of a run and writes them
+/// as a single pretty-printed `JsonReport` document once the run is finished.
+///
+/// # Examples
+///
+/// ```no_run
+/// use cargo_culture_kit::{check_culture, HasLicenseFile, IsSuccess, JsonReporter};
+/// use std::path::PathBuf;
+///
+/// let rule = HasLicenseFile::default();
+/// let cargo_manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+///         .join("Cargo.toml");
+///
+/// let mut reporter = JsonReporter::new(Vec::new());
+/// let outcomes = check_culture(cargo_manifest, false, &mut reporter, &[&rule])
+///     .expect("Unexpected trouble checking culture rules: ");
+///
+/// assert!(outcomes.is_success());
+/// let json_output = String::from_utf8(reporter.into_inner()).unwrap();
+/// assert!(json_output.contains("\"success\""));
+/// ```
+#[derive(Debug)]
+pub struct JsonReporter<W: Write> {
+    output: W,
+    evaluations: Vec<JsonRuleEvaluation>,
+}
+
+impl<W: Write> JsonReporter<W> {
+    /// Create a `JsonReporter` that writes its document to the supplied
+    /// `Write` instance.
+    pub fn new(output: W) -> Self {
+        JsonReporter {
+            output,
+            evaluations: Vec::new(),
+        }
+    }
+
+    /// Unwrap this `JsonReporter`, returning the underlying `Write` instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+impl<W: Write> Reporter for JsonReporter<W> {
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError> {
+        self.evaluations = Vec::with_capacity(rules.len());
+        Ok(())
+    }
+
+    fn rule_started(&mut self, _rule: &Rule) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, _rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        self.evaluations.push(JsonRuleEvaluation {
+            id: report.id.clone(),
+            description: report.description.clone(),
+            severity: report.severity,
+            outcome: report.outcome.clone(),
+            duration_secs: report.duration.as_secs() as f64
+                + f64::from(report.duration.subsec_nanos()) / 1e9,
+            details: report.details.clone(),
+            findings: report.findings.clone(),
+            cached: report.cached,
+        });
+        Ok(())
+    }
+
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError> {
+        let stats = OutcomeStats::from(report);
+        let report = JsonReport {
+            outcome: RuleOutcome::from(&stats),
+            rules: self.evaluations.drain(..).collect(),
+            stats,
+        };
+        write_json_report(&report, &mut self.output)
+    }
+}
+
+/// Evaluate the supplied `Rule`s in the same fashion as `check_culture`,
+/// but instead of printing human-oriented prose, write a single
+/// `JsonReport` document to `json_output`.
+///
+/// A shorthand for `check_culture` with a `JsonReporter`.
+///
+/// The returned `CultureReport` keeps the `Severity` of each `Rule`, so that
+/// the failures of advisory `Rule`s do not count against its `is_success`
+/// or `exit_code`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use cargo_culture_kit::{check_culture_json, ExitCode, HasLicenseFile, IsSuccess};
+/// use std::path::PathBuf;
+///
+/// let rule = HasLicenseFile::default();
+/// let cargo_manifest = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+///         .join("Cargo.toml");
+///
+/// let mut json_output: Vec<u8> = Vec::new();
+/// let report = check_culture_json(cargo_manifest, false, &mut json_output, &[&rule])
+///     .expect("Unexpected trouble checking culture rules: ");
+///
+/// assert!(report.is_success());
+/// assert_eq!(0, report.exit_code());
+/// assert!(String::from_utf8(json_output).unwrap().contains("\"success\""));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the program cannot write to the supplied `json_output`
+/// instance.
+pub fn check_culture_json<P: AsRef<Path>, W: Write>(
+    cargo_manifest_file_path: P,
+    verbose: bool,
+    json_output: &mut W,
+    rules: &[&Rule],
+) -> Result<CultureReport, CheckError> {
+    let mut reporter = JsonReporter::new(json_output);
+    check_culture(cargo_manifest_file_path, verbose, &mut reporter, rules)
+}
+
+/// Serialize the `report` as a JSON document to the supplied `Write` instance.
+///
+/// # Errors
+///
+/// Returns an error if the program cannot write to the supplied `json_output`
+/// instance.
+pub fn write_json_report<W: Write>(
+    report: &JsonReport,
+    json_output: &mut W,
+) -> Result<(), CheckError> {
+    if serde_json::to_writer_pretty(&mut *json_output, report)
+        .map_err(|_| ())
+        .and_then(|_| writeln!(json_output).map_err(|_| ()))
+        .is_err()
+    {
+        return Err(CheckError::PrintOutputFailure {
+            topic: "JSON report",
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::check_culture;
+    use super::*;
+    use serde_json::Value;
+    use tempfile::tempdir;
+    use {ExitCode, IsSuccess, RuleContext};
+
+    #[derive(Debug)]
+    struct QuietSucceedingRule;
+
+    impl Rule for QuietSucceedingRule {
+        fn description(&self) -> &str {
+            "Should be a rule that succeeds quietly."
+        }
+
+        fn evaluate(&self, _context: RuleContext) -> RuleOutcome {
+            RuleOutcome::Success
+        }
+    }
+
+    #[derive(Debug)]
+    struct ChattyFailingRule;
+
+    impl Rule for ChattyFailingRule {
+        fn id(&self) -> &str {
+            "chatty-failing"
+        }
+
+        fn description(&self) -> &str {
+            "Should be a rule that fails loudly."
+        }
+
+        fn evaluate(&self, context: RuleContext) -> RuleOutcome {
+            if context.verbose {
+                let _ = write!(context.print_output, "Here is why.");
+            }
+            RuleOutcome::Failure
+        }
+    }
+
+    #[derive(Debug)]
+    struct AdvisoryFailingRule;
+
+    impl Rule for AdvisoryFailingRule {
+        fn description(&self) -> &str {
+            "Should be a rule whose failure is only advisory."
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Warning
+        }
+
+        fn evaluate(&self, _context: RuleContext) -> RuleOutcome {
+            RuleOutcome::Failure
+        }
+    }
+
+    fn check_to_json_value(verbose: bool, rules: &[&Rule]) -> Value {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let mut reporter = JsonReporter::new(Vec::new());
+        let _ = check_culture(dir.path().join("Cargo.toml"), verbose, &mut reporter, rules)
+            .expect("Expect no trouble with eval");
+        serde_json::from_slice(&reporter.into_inner()).expect("Output should be well-formed JSON")
+    }
+
+    #[test]
+    fn json_report_has_rules_in_order_and_stats() {
+        let quiet = QuietSucceedingRule;
+        let chatty = ChattyFailingRule;
+        let value = check_to_json_value(true, &[&quiet, &chatty]);
+        assert_eq!("failure", value["outcome"]);
+        assert_eq!(quiet.description(), value["rules"][0]["description"]);
+        assert_eq!(quiet.description(), value["rules"][0]["id"]);
+        assert_eq!("success", value["rules"][0]["outcome"]);
+        assert_eq!(chatty.description(), value["rules"][1]["description"]);
+        assert_eq!("chatty-failing", value["rules"][1]["id"]);
+        assert_eq!("failure", value["rules"][1]["outcome"]);
+        assert_eq!("error", value["rules"][1]["severity"]);
+        assert_eq!("Here is why.", value["rules"][1]["details"]);
+        assert!(value["rules"][1]["duration_secs"].is_f64());
+        assert_eq!(1, value["stats"]["success_count"]);
+        assert_eq!(1, value["stats"]["fail_count"]);
+        assert_eq!(0, value["stats"]["undetermined_count"]);
+        assert_eq!(0, value["stats"]["warning_count"]);
+        assert_eq!(0, value["stats"]["info_count"]);
+    }
+
+    #[test]
+    fn json_report_omits_details_when_not_verbose() {
+        let chatty = ChattyFailingRule;
+        let value = check_to_json_value(false, &[&chatty]);
+        assert_eq!("", value["rules"][0]["details"]);
+    }
+
+    #[test]
+    fn check_culture_json_writes_the_same_document_as_json_reporter() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let chatty = ChattyFailingRule;
+        let mut json_output: Vec<u8> = Vec::new();
+        let report = check_culture_json(
+            dir.path().join("Cargo.toml"),
+            true,
+            &mut json_output,
+            &[&chatty],
+        )
+        .expect("Expect no trouble with eval");
+        assert_eq!(
+            Some(&RuleOutcome::Failure),
+            report.get(chatty.description()).map(|r| &r.outcome)
+        );
+        let value: Value =
+            serde_json::from_slice(&json_output).expect("Output should be well-formed JSON");
+        assert_eq!(
+            check_to_json_value(true, &[&chatty])["rules"][0]["id"],
+            value["rules"][0]["id"]
+        );
+        assert_eq!("failure", value["outcome"]);
+    }
+
+    #[test]
+    fn check_culture_json_does_not_hold_advisory_failures_against_the_check() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let report = check_culture_json(
+            dir.path().join("Cargo.toml"),
+            false,
+            &mut Vec::new(),
+            &[&QuietSucceedingRule, &AdvisoryFailingRule],
+        )
+        .expect("Expect no trouble with eval");
+        assert!(report.is_success());
+        assert_eq!(0, report.exit_code());
+        assert_eq!(1, OutcomeStats::from(&report).warning_count);
+    }
+
+    #[test]
+    fn json_report_for_no_rules_is_undetermined() {
+        let value = check_to_json_value(false, &[]);
+        assert_eq!("undetermined", value["outcome"]);
+        assert_eq!(Some(0), value["rules"].as_array().map(|a| a.len()));
+    }
+}
@@ -0,0 +1,259 @@
+//! JUnit XML reporting of `Rule` evaluation results.
+//!
+//! Each `Rule` is represented as a `<testcase>` named by its description,
+//! so that CI systems which natively render JUnit XML can display culture
+//! regressions alongside ordinary test results.
+use super::super::{
+    CheckError, CultureReport, OutcomeStats, Rule, RuleOutcome, RuleReport, Severity,
+};
+use super::{advisory_severity, escape_xml, Reporter};
+use std::io::Write;
+use std::time::Duration;
+
+const SUITE_NAME: &str = "cargo-culture";
+const FAILURE_MESSAGE: &str = "The rule was not upheld for this project.";
+const ERROR_MESSAGE: &str = "Could not determine whether the rule was upheld for this project.";
+const ADVISORY_MESSAGE: &str = "The advisory rule was not upheld for this project.";
+const SKIPPED_MESSAGE: &str = "A prerequisite of the rule was not upheld for this project.";
+
+/// `Reporter` that collects the results of a run and writes them as a
+/// JUnit XML document once the run is finished.
+///
+/// `RuleOutcome::Failure` is represented as a `<failure>`, and
+/// `RuleOutcome::Undetermined` as an `<error>`, either of which lists
+/// any `Finding`s the `Rule` supplied. Advisory `Rule`s of
+/// `Severity::Warning` or `Severity::Info` that were not upheld are instead
+/// represented as `<skipped>`, so they do not fail the suite, as are `Rule`s
+/// whose outcome is `RuleOutcome::Skipped`. Any verbose details
+/// written by a `Rule` are attached as the test case's `<system-out>`.
+#[derive(Debug)]
+pub struct JunitReporter<W: Write> {
+    output: W,
+    diagnostics: Vec<String>,
+    test_cases: Vec<JunitTestCase>,
+}
+
+#[derive(Debug)]
+struct JunitTestCase {
+    name: String,
+    outcome: RuleOutcome,
+    advisory_severity: Option<Severity>,
+    time: Duration,
+    findings: Vec<String>,
+    system_out: String,
+}
+
+impl<W: Write> JunitReporter<W> {
+    /// Create a `JunitReporter` that writes its document to the supplied
+    /// `Write` instance.
+    pub fn new(output: W) -> Self {
+        JunitReporter {
+            output,
+            diagnostics: Vec::new(),
+            test_cases: Vec::new(),
+        }
+    }
+
+    /// Unwrap this `JunitReporter`, returning the underlying `Write` instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_document(&mut self, stats: &OutcomeStats, time: Duration) -> ::std::io::Result<()> {
+        let skipped = stats.warning_count + stats.info_count + stats.skipped_count;
+        let tests = stats.success_count + stats.fail_count + stats.undetermined_count + skipped;
+        writeln!(self.output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            self.output,
+            r#"<testsuites name="{}" tests="{}" failures="{}" errors="{}" time="{}">"#,
+            SUITE_NAME,
+            tests,
+            stats.fail_count,
+            stats.undetermined_count,
+            seconds(time)
+        )?;
+        writeln!(
+            self.output,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" \
+             skipped=\"{}\" time=\"{}\">",
+            SUITE_NAME,
+            tests,
+            stats.fail_count,
+            stats.undetermined_count,
+            skipped,
+            seconds(time)
+        )?;
+        for test_case in &self.test_cases {
+            writeln!(
+                self.output,
+                r#"    <testcase name="{}" classname="{}" time="{}">"#,
+                escape_xml(&test_case.name),
+                SUITE_NAME,
+                seconds(test_case.time)
+            )?;
+            let problem = match (&test_case.outcome, test_case.advisory_severity) {
+                (RuleOutcome::Success, _) => None,
+                (RuleOutcome::Skipped, _) => Some(("skipped", SKIPPED_MESSAGE, "prerequisite")),
+                (_, Some(Severity::Info)) => Some(("skipped", ADVISORY_MESSAGE, "info")),
+                (_, Some(_)) => Some(("skipped", ADVISORY_MESSAGE, "warning")),
+                (RuleOutcome::Failure, None) => Some(("failure", FAILURE_MESSAGE, "failure")),
+                (RuleOutcome::Undetermined, None) => {
+                    Some(("error", ERROR_MESSAGE, "undetermined"))
+                }
+            };
+            match problem {
+                None => (),
+                Some((element, message, kind)) if test_case.findings.is_empty() => writeln!(
+                    self.output,
+                    r#"      <{} message="{}" type="{}"/>"#,
+                    element, message, kind
+                )?,
+                Some((element, message, kind)) => writeln!(
+                    self.output,
+                    r#"      <{0} message="{1}" type="{2}">{3}</{0}>"#,
+                    element,
+                    message,
+                    kind,
+                    escape_xml(&test_case.findings.join("\n"))
+                )?,
+            }
+            if !test_case.system_out.is_empty() {
+                writeln!(
+                    self.output,
+                    "      <system-out>{}</system-out>",
+                    escape_xml(&test_case.system_out)
+                )?;
+            }
+            writeln!(self.output, "    </testcase>")?;
+        }
+        if !self.diagnostics.is_empty() {
+            writeln!(
+                self.output,
+                "    <system-out>{}</system-out>",
+                escape_xml(&self.diagnostics.join("\n"))
+            )?;
+        }
+        writeln!(self.output, "  </testsuite>")?;
+        writeln!(self.output, "</testsuites>")?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Reporter for JunitReporter<W> {
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError> {
+        self.diagnostics.clear();
+        self.test_cases = Vec::with_capacity(rules.len());
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, message: &str) -> Result<(), CheckError> {
+        self.diagnostics.push(message.to_owned());
+        Ok(())
+    }
+
+    fn rule_started(&mut self, _rule: &Rule) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        self.test_cases.push(JunitTestCase {
+            name: rule.description().to_owned(),
+            outcome: report.outcome.clone(),
+            advisory_severity: advisory_severity(report),
+            time: report.duration,
+            findings: report.findings.iter().map(|f| f.to_string()).collect(),
+            system_out: report.details.clone(),
+        });
+        Ok(())
+    }
+
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError> {
+        let stats = OutcomeStats::from(report);
+        if self.write_document(&stats, report.total_duration()).is_err() {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "JUnit report",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Render a `Duration` in the fractional seconds JUnit expects, e.g. "1.500"
+fn seconds(duration: Duration) -> String {
+    format!("{}.{:03}", duration.as_secs(), duration.subsec_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use Finding;
+
+    fn report(rules_and_details: &[(&FixedRule, &str)]) -> String {
+        let mut reporter = JunitReporter::new(Vec::new());
+        simulate_run(&mut reporter, rules_and_details);
+        String::from_utf8(reporter.into_inner()).expect("Output should be utf8")
+    }
+
+    #[test]
+    fn junit_maps_outcomes_to_elements() {
+        let pass = FixedRule("Should pass.", RuleOutcome::Success);
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let unknown = FixedRule("Should be unknown.", RuleOutcome::Undetermined);
+        let xml = report(&[(&pass, ""), (&fail, "Some details"), (&unknown, "")]);
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains(r#"tests="3" failures="1" errors="1""#));
+        assert!(xml.contains(r#"<testsuite name="cargo-culture" tests="3""#));
+        assert!(xml.contains(r#"time="4.500">"#));
+        assert!(xml.contains(
+            r#"<testcase name="Should pass." classname="cargo-culture" time="1.500">"#
+        ));
+        assert_eq!(1, xml.matches("<failure ").count());
+        assert_eq!(1, xml.matches("<error ").count());
+        assert!(xml.contains("<system-out>Some details</system-out>"));
+    }
+
+    #[test]
+    fn junit_skips_advisory_rules() {
+        let pass = FixedRule("Should pass.", RuleOutcome::Success);
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut advisory_report = fixed_report(&fail, "");
+        advisory_report.severity = Severity::Warning;
+        let mut reporter = JunitReporter::new(Vec::new());
+        simulate_run_with_reports(
+            &mut reporter,
+            &[(&pass, fixed_report(&pass, "")), (&fail, advisory_report)],
+        );
+        let xml = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(xml.contains(r#"tests="2" failures="0" errors="0" skipped="1""#));
+        assert_eq!(0, xml.matches("<failure ").count());
+        assert!(xml.contains(r#"<skipped message="The advisory rule"#));
+    }
+
+    #[test]
+    fn junit_lists_findings_in_failure() {
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "");
+        report.findings.push(Finding::new("<a>"));
+        report.findings.push(Finding::new("b"));
+        let mut reporter = JunitReporter::new(Vec::new());
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let xml = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(xml.contains(&format!(
+            r#"<failure message="{}" type="failure">&lt;a&gt;
+b</failure>"#,
+            FAILURE_MESSAGE
+        )));
+    }
+
+    #[test]
+    fn junit_escapes_descriptions_and_details() {
+        let rule = FixedRule(
+            "Should `cargo build` <cleanly> & \"quietly\".",
+            RuleOutcome::Failure,
+        );
+        let xml = report(&[(&rule, "warning: <unused>")]);
+        assert!(xml.contains("Should `cargo build` &lt;cleanly&gt; &amp; &quot;quietly&quot;."));
+        assert!(xml.contains("<system-out>warning: &lt;unused&gt;</system-out>"));
+    }
+}
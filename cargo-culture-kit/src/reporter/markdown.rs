@@ -0,0 +1,195 @@
+//! Markdown reporting of `Rule` evaluation results.
+//!
+//! The document is intended to be readable as plain text as well as when
+//! rendered, e.g. as the body of a pull request comment.
+use super::super::{
+    CheckError, CultureReport, IsSuccess, OutcomeStats, Rule, RuleOutcome, RuleReport,
+};
+use super::{advisory_summary, outcome_label, DocumentRow, Reporter};
+use std::io::Write;
+
+/// `Reporter` that collects the results of a run and writes them as a
+/// Markdown document once the run is finished.
+///
+/// The document contains a summary of the `OutcomeStats` followed by a table
+/// of every `Rule` with its outcome, justification, any `Finding`s and any
+/// verbose details.
+#[derive(Debug)]
+pub struct MarkdownReporter<W: Write> {
+    output: W,
+    diagnostics: Vec<String>,
+    rows: Vec<DocumentRow>,
+}
+
+impl<W: Write> MarkdownReporter<W> {
+    /// Create a `MarkdownReporter` that writes its document to the supplied
+    /// `Write` instance.
+    pub fn new(output: W) -> Self {
+        MarkdownReporter {
+            output,
+            diagnostics: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Unwrap this `MarkdownReporter`, returning the underlying `Write`
+    /// instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_document(&mut self, stats: &OutcomeStats) -> ::std::io::Result<()> {
+        writeln!(self.output, "# Culture report")?;
+        writeln!(self.output)?;
+        writeln!(
+            self.output,
+            "**culture result: {}.** {} passed. {} failed. {} undetermined.{}",
+            if stats.is_success() { "ok" } else { "FAILED" },
+            stats.success_count,
+            stats.fail_count,
+            stats.undetermined_count,
+            advisory_summary(stats)
+        )?;
+        writeln!(self.output)?;
+        writeln!(self.output, "| Rule | Outcome | Justification | Details |")?;
+        writeln!(self.output, "| --- | --- | --- | --- |")?;
+        for row in &self.rows {
+            writeln!(
+                self.output,
+                "| {} | {} | {} | {} |",
+                escape_cell(&row.description),
+                outcome_cell(row),
+                escape_cell(row.justification.as_ref().map_or("", |j| j.as_str())),
+                details_cell(row)
+            )?;
+        }
+        if !self.diagnostics.is_empty() {
+            writeln!(self.output)?;
+            writeln!(self.output, "## Diagnostics")?;
+            writeln!(self.output)?;
+            for diagnostic in &self.diagnostics {
+                writeln!(self.output, "* {}", escape_cell(diagnostic))?;
+            }
+        }
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Reporter for MarkdownReporter<W> {
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError> {
+        self.diagnostics.clear();
+        self.rows = Vec::with_capacity(rules.len());
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, message: &str) -> Result<(), CheckError> {
+        self.diagnostics.push(message.trim().to_owned());
+        Ok(())
+    }
+
+    fn rule_started(&mut self, _rule: &Rule) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        self.rows.push(DocumentRow::new(rule, report));
+        Ok(())
+    }
+
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError> {
+        let stats = OutcomeStats::from(report);
+        if self.write_document(&stats).is_err() {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "Markdown report",
+            });
+        }
+        Ok(())
+    }
+}
+
+fn outcome_cell(row: &DocumentRow) -> String {
+    match (&row.outcome, row.advisory_severity) {
+        (RuleOutcome::Success, _) => outcome_label(&row.outcome).to_owned(),
+        (_, Some(severity)) => format!("{} ({})", outcome_label(&row.outcome), severity),
+        (_, None) => format!("**{}**", outcome_label(&row.outcome)),
+    }
+}
+
+fn details_cell(row: &DocumentRow) -> String {
+    let mut lines: Vec<String> = row.findings.iter().map(|f| format!("* {}", f)).collect();
+    if !row.details.is_empty() {
+        lines.push(row.details.clone());
+    }
+    escape_cell(&lines.join("\n"))
+}
+
+/// Escape text so that it occupies a single Markdown table cell,
+/// rendering line breaks as HTML `<br>` elements.
+fn escape_cell(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.trim().chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '|' => escaped.push_str("\\|"),
+            '\n' => escaped.push_str("<br>"),
+            '\r' => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use {Finding, Severity};
+
+    fn report(rules_and_details: &[(&FixedRule, &str)]) -> String {
+        let mut reporter = MarkdownReporter::new(Vec::new());
+        simulate_run(&mut reporter, rules_and_details);
+        String::from_utf8(reporter.into_inner()).expect("Output should be utf8")
+    }
+
+    #[test]
+    fn markdown_report_has_summary_and_row_per_rule() {
+        let pass = FixedRule("Should pass.", RuleOutcome::Success);
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let markdown = report(&[(&pass, ""), (&fail, "Some details")]);
+        assert!(markdown.contains("**culture result: FAILED.** 1 passed. 1 failed."));
+        assert!(markdown.contains("| Should pass. | ok | Because. |  |\n"));
+        assert!(markdown.contains("| Should fail. | **FAILED** | Because. | Some details |\n"));
+    }
+
+    #[test]
+    fn markdown_details_include_findings() {
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "Some details");
+        report.findings.push(Finding::new("Nope").with_file("a|b"));
+        report.findings.push(Finding::new("Still no"));
+        let mut reporter = MarkdownReporter::new(Vec::new());
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let markdown = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(markdown.contains("| * a\\|b: Nope<br>* Still no<br>Some details |\n"));
+    }
+
+    #[test]
+    fn markdown_advisory_failures_are_not_emphasized() {
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "");
+        report.severity = Severity::Warning;
+        let mut reporter = MarkdownReporter::new(Vec::new());
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let markdown = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(markdown.contains("**culture result: ok.** 0 passed."));
+        assert!(markdown.contains("0 undetermined. 1 warnings. 0 info."));
+        assert!(markdown.contains("| Should fail. | FAILED (warning) | Because. |  |\n"));
+    }
+
+    #[test]
+    fn markdown_cells_stay_on_one_line() {
+        assert_eq!("a \\| b<br>&lt;c&gt;", escape_cell("a | b\r\n<c>\n"));
+    }
+}
@@ -0,0 +1,247 @@
+//! Provides the `Reporter` trait, which receives notifications about the
+//! progress of a `check_culture` run, and several implementations.
+//!
+//! `TextReporter` produces the classic human-oriented prose and
+//! `TapReporter` a Test Anything Protocol stream,
+//! while `JsonReporter`, `JunitReporter` and `SarifReporter` each produce
+//! a single machine-readable document.
+//! `MarkdownReporter` and `HtmlReporter` produce human-oriented documents
+//! suitable for sharing outside the terminal.
+mod html;
+mod json;
+mod junit;
+mod markdown;
+mod sarif;
+mod tap;
+mod text;
+
+pub use self::html::HtmlReporter;
+pub use self::json::{
+    check_culture_json, write_json_report, JsonReport, JsonReporter, JsonRuleEvaluation,
+};
+pub use self::junit::JunitReporter;
+pub use self::markdown::MarkdownReporter;
+pub use self::sarif::SarifReporter;
+pub use self::tap::TapReporter;
+pub use self::text::TextReporter;
+
+use super::{CheckError, CultureReport, OutcomeStats, Rule, RuleOutcome, RuleReport, Severity};
+
+/// Receives notifications about the progress of a `check_culture` run
+/// and is responsible for presenting those results to the end user,
+/// or to whatever tooling sits downstream.
+///
+/// For a given run, the methods are invoked in the following order:
+///
+/// * `run_started` once
+/// * `diagnostic` zero or more times
+/// * `rule_started` followed by `rule_finished` for each `Rule`
+/// * `run_finished` once
+pub trait Reporter {
+    /// Invoked once, before any `Rule` is evaluated, with the complete set of
+    /// `Rule`s that are about to be evaluated.
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError>;
+
+    /// Supplies human-oriented information that is not specific to any
+    /// single `Rule`, such as trouble reading the project's cargo metadata.
+    ///
+    /// Only supplied when `verbose` checking was requested.
+    /// Ignored by default.
+    fn diagnostic(&mut self, _message: &str) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    /// Invoked immediately before `rule` is evaluated, or, when `Rule`s are
+    /// evaluated concurrently, once the evaluations of `rule` and of every
+    /// preceding `Rule` are complete.
+    fn rule_started(&mut self, rule: &Rule) -> Result<(), CheckError>;
+
+    /// Invoked after `rule` has been evaluated, with the result of that
+    /// evaluation.
+    ///
+    /// `report.details` contains any content the `Rule` wrote to its
+    /// `RuleContext::print_output`, and is typically empty unless
+    /// `verbose` checking was requested.
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError>;
+
+    /// Invoked once, after all `Rule`s have been evaluated, with the same
+    /// `CultureReport` that `check_culture` returns.
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError>;
+}
+
+/// A single `Rule`'s row in the table of a human-oriented document report.
+#[derive(Debug)]
+struct DocumentRow {
+    description: String,
+    justification: Option<String>,
+    outcome: RuleOutcome,
+    advisory_severity: Option<Severity>,
+    findings: Vec<String>,
+    details: String,
+}
+
+impl DocumentRow {
+    fn new(rule: &Rule, report: &RuleReport) -> Self {
+        DocumentRow {
+            description: report.description.clone(),
+            justification: rule.justification().map(|j| j.to_owned()),
+            outcome: report.outcome.clone(),
+            advisory_severity: advisory_severity(report),
+            findings: report.findings.iter().map(|f| f.to_string()).collect(),
+            details: report.details.trim().to_owned(),
+        }
+    }
+}
+
+/// The uncolored equivalent of the outcome summaries printed by `TextReporter`
+fn outcome_label(outcome: &RuleOutcome) -> &'static str {
+    match *outcome {
+        RuleOutcome::Success => "ok",
+        RuleOutcome::Failure => "FAILED",
+        RuleOutcome::Undetermined => "UNDETERMINED",
+        RuleOutcome::Skipped => "SKIPPED",
+    }
+}
+
+/// The `Severity` of a `Rule` that was not upheld, but whose outcome does
+/// not count against the overall result because it is merely advisory
+fn advisory_severity(report: &RuleReport) -> Option<Severity> {
+    match (&report.outcome, report.severity) {
+        (RuleOutcome::Success, _) | (RuleOutcome::Skipped, _) | (_, Severity::Error) => None,
+        (_, severity) => Some(severity),
+    }
+}
+
+/// The counts of advisory `Rule`s that were not upheld, and of skipped
+/// `Rule`s, to be appended to a summary of `OutcomeStats`, or nothing when
+/// there were none
+fn advisory_summary(stats: &OutcomeStats) -> String {
+    let mut summary = String::new();
+    if stats.warning_count != 0 || stats.info_count != 0 {
+        summary.push_str(&format!(
+            " {} warnings. {} info.",
+            stats.warning_count, stats.info_count
+        ));
+    }
+    if stats.skipped_count != 0 {
+        summary.push_str(&format!(" {} skipped.", stats.skipped_count));
+    }
+    summary
+}
+
+/// Escape text for inclusion in XML attribute values or character data,
+/// dropping any characters that XML 1.0 does not permit at all.
+fn escape_xml(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => (),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::super::{CultureReport, Rule, RuleContext, RuleOutcome, RuleReport};
+    use super::Reporter;
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    pub struct FixedRule(pub &'static str, pub RuleOutcome);
+
+    impl Rule for FixedRule {
+        fn description(&self) -> &str {
+            self.0
+        }
+
+        fn justification(&self) -> Option<&str> {
+            Some("Because.")
+        }
+
+        fn evaluate(&self, _context: RuleContext) -> RuleOutcome {
+            self.1.clone()
+        }
+    }
+
+    /// A `RuleReport` as though `rule` had been evaluated to `outcome`
+    /// instantly, without details or findings
+    pub fn rule_report(rule: &Rule, outcome: RuleOutcome) -> RuleReport {
+        RuleReport {
+            id: rule.id().to_owned(),
+            description: rule.description().to_owned(),
+            severity: rule.severity(),
+            outcome,
+            duration: Duration::from_secs(0),
+            details: String::new(),
+            findings: Vec::new(),
+            cached: false,
+        }
+    }
+
+    /// A `RuleReport` as though `rule` had been evaluated, producing `details`
+    pub fn fixed_report(rule: &FixedRule, details: &str) -> RuleReport {
+        RuleReport {
+            duration: Duration::from_millis(1500),
+            details: details.to_owned(),
+            ..rule_report(rule, rule.1.clone())
+        }
+    }
+
+    /// Drive `reporter` through a run as though each `FixedRule` had been
+    /// evaluated, producing the paired details.
+    pub fn simulate_run<R: Reporter>(reporter: &mut R, rules_and_details: &[(&FixedRule, &str)]) {
+        let rules_and_reports: Vec<(&FixedRule, RuleReport)> = rules_and_details
+            .iter()
+            .map(|&(rule, details)| (rule, fixed_report(rule, details)))
+            .collect();
+        simulate_run_with_reports(reporter, &rules_and_reports);
+    }
+
+    /// Drive `reporter` through a run as though each `FixedRule` had been
+    /// evaluated, producing the paired `RuleReport`.
+    pub fn simulate_run_with_reports<R: Reporter>(
+        reporter: &mut R,
+        rules_and_reports: &[(&FixedRule, RuleReport)],
+    ) {
+        let rules: Vec<&Rule> = rules_and_reports.iter().map(|&(r, _)| r as &Rule).collect();
+        let mut report = CultureReport::default();
+        reporter.run_started(&rules).expect("Should start");
+        for &(rule, ref rule_report) in rules_and_reports {
+            reporter.rule_started(rule).expect("Should start rule");
+            reporter
+                .rule_finished(rule, rule_report)
+                .expect("Should finish rule");
+            report.rules.push(rule_report.clone());
+        }
+        reporter.run_finished(&report).expect("Should finish");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn escape_xml_leaves_no_markup_characters(ref raw in ".*") {
+            let escaped = escape_xml(raw);
+            prop_assert!(!escaped.contains('<'));
+            prop_assert!(!escaped.contains('>'));
+            prop_assert!(!escaped.contains('"'));
+            prop_assert!(!escaped.contains('\''));
+        }
+    }
+
+    #[test]
+    fn escape_xml_drops_disallowed_control_characters() {
+        assert_eq!("a\tb\nc", escape_xml("a\tb\u{1b}\nc"));
+    }
+}
@@ -0,0 +1,393 @@
+//! SARIF 2.1.0 reporting of `Rule` evaluation results.
+//!
+//! Each `Rule` is represented as a SARIF rule descriptor, and each
+//! non-successful `RuleOutcome` as a SARIF result, so that culture findings
+//! can be displayed by code-scanning integrations alongside other static
+//! analysis.
+use super::super::{
+    CheckError, CultureReport, Finding, Rule, RuleOutcome, RuleReport, Severity,
+};
+use super::Reporter;
+use serde_json::{self, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const PROJECT_ROOT_BASE_ID: &str = "PROJECTROOT";
+
+/// `Reporter` that collects the results of a run and writes them as a
+/// SARIF 2.1.0 log once the run is finished.
+///
+/// `RuleOutcome::Failure` results are reported at the `error` level and
+/// `RuleOutcome::Undetermined` results at the `warning` level. Each is
+/// lowered by one level, down to `note`, for `Rule`s of `Severity::Warning`,
+/// and `Rule`s of `Severity::Info` are always reported at the `note` level.
+/// Successful `Rule`s produce no results. Each `Finding` that concerns a
+/// particular file is reported as a result located at that file, while
+/// the remaining `Finding`s and any verbose details are reported in a
+/// result located at the project's `Cargo.toml` file.
+#[derive(Debug)]
+pub struct SarifReporter<W: Write> {
+    output: W,
+    cargo_manifest_file_path: PathBuf,
+    rule_descriptors: Vec<Value>,
+    results: Vec<Value>,
+}
+
+impl<W: Write> SarifReporter<W> {
+    /// Create a `SarifReporter` that writes its log to the supplied
+    /// `Write` instance.
+    ///
+    /// `cargo_manifest_file_path` should be the same project manifest supplied
+    /// to `check_culture`, and is used to locate results.
+    pub fn new<P: AsRef<Path>>(output: W, cargo_manifest_file_path: P) -> Self {
+        SarifReporter {
+            output,
+            cargo_manifest_file_path: cargo_manifest_file_path.as_ref().to_path_buf(),
+            rule_descriptors: Vec::new(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Unwrap this `SarifReporter`, returning the underlying `Write` instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn project_dir(&self) -> PathBuf {
+        self.canonical_manifest()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_default()
+    }
+
+    fn canonical_manifest(&self) -> PathBuf {
+        self.cargo_manifest_file_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.cargo_manifest_file_path.clone())
+    }
+
+    fn manifest_location(&self) -> (String, String) {
+        let manifest = self.canonical_manifest();
+        let project_dir = self.project_dir();
+        let file_name = manifest
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("Cargo.toml"));
+        let mut project_uri = path_to_file_uri(&project_dir);
+        if !project_uri.ends_with('/') {
+            project_uri.push('/');
+        }
+        (project_uri, encode_uri_path(&file_name))
+    }
+
+    /// Locate a `Finding` relative to the project root where possible,
+    /// falling back to an absolute file URI.
+    fn finding_location(&self, finding: &Finding) -> Value {
+        let file = finding.file.as_deref().unwrap_or(Path::new(""));
+        let project_dir = self.project_dir();
+        let relative = if file.is_absolute() {
+            file.strip_prefix(&project_dir).ok().map(|p| p.to_path_buf())
+        } else {
+            Some(file.to_path_buf())
+        };
+        let artifact_location = match relative {
+            Some(r) => json!({
+                "uri": encode_uri_path(&r.to_string_lossy().replace('\\', "/")),
+                "uriBaseId": PROJECT_ROOT_BASE_ID,
+            }),
+            None => json!({ "uri": path_to_file_uri(file) }),
+        };
+        let mut physical_location = json!({ "artifactLocation": artifact_location });
+        if let Some(line) = finding.line {
+            physical_location["region"] = json!({ "startLine": line });
+        }
+        json!({ "physicalLocation": physical_location })
+    }
+
+    fn push_result(
+        &mut self,
+        rule_id: &str,
+        rule_index: Option<usize>,
+        level: &str,
+        text: &str,
+        location: Value,
+    ) {
+        let mut result = json!({
+            "ruleId": rule_id,
+            "level": level,
+            "message": { "text": text },
+            "locations": [location],
+        });
+        if let Some(index) = rule_index {
+            result["ruleIndex"] = Value::from(index);
+        }
+        self.results.push(result);
+    }
+}
+
+impl<W: Write> Reporter for SarifReporter<W> {
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError> {
+        self.rule_descriptors = rules
+            .iter()
+            .map(|r| {
+                json!({
+                    "id": rule_id(*r),
+                    "shortDescription": { "text": r.description() },
+                })
+            })
+            .collect();
+        self.results.clear();
+        Ok(())
+    }
+
+    fn rule_started(&mut self, _rule: &Rule) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        let summary = match report.outcome {
+            // A skipped rule's findings point at its failed prerequisite,
+            // which has results of its own
+            RuleOutcome::Success | RuleOutcome::Skipped => return Ok(()),
+            RuleOutcome::Failure => "This project does not uphold the rule",
+            RuleOutcome::Undetermined => {
+                "Could not determine whether this project upholds the rule"
+            }
+        };
+        let level = match (&report.outcome, report.severity) {
+            (RuleOutcome::Failure, Severity::Error) => "error",
+            (RuleOutcome::Failure, Severity::Warning) | (_, Severity::Error) => "warning",
+            _ => "note",
+        };
+        let id = rule_id(rule);
+        let rule_index = self.rule_descriptors
+            .iter()
+            .position(|d| d["id"] == id.as_str());
+        let (located, unlocated): (Vec<&Finding>, Vec<&Finding>) =
+            report.findings.iter().partition(|f| f.file.is_some());
+        for finding in &located {
+            let text = format!("{}: {}", rule.description(), finding.message);
+            let location = self.finding_location(finding);
+            self.push_result(&id, rule_index, level, &text, location);
+        }
+        if located.is_empty() || !unlocated.is_empty() {
+            let mut text = format!("{}: {}", summary, rule.description());
+            for finding in &unlocated {
+                text.push_str("\n* ");
+                text.push_str(&finding.message);
+            }
+            if !report.details.trim().is_empty() {
+                text.push_str("\n\n");
+                text.push_str(report.details.trim());
+            }
+            let (_, manifest_uri) = self.manifest_location();
+            let location = json!({
+                "physicalLocation": {
+                    "artifactLocation": {
+                        "uri": manifest_uri,
+                        "uriBaseId": PROJECT_ROOT_BASE_ID,
+                    }
+                }
+            });
+            self.push_result(&id, rule_index, level, &text, location);
+        }
+        Ok(())
+    }
+
+    fn run_finished(&mut self, _report: &CultureReport) -> Result<(), CheckError> {
+        let (project_uri, _) = self.manifest_location();
+        let log = json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "cargo-culture",
+                        "informationUri": "https://github.com/PolySync/cargo-culture",
+                        "rules": self.rule_descriptors,
+                    }
+                },
+                "originalUriBaseIds": {
+                    PROJECT_ROOT_BASE_ID: { "uri": project_uri }
+                },
+                "results": self.results,
+            }],
+        });
+        if serde_json::to_writer_pretty(&mut self.output, &log)
+            .map_err(|_| ())
+            .and_then(|_| writeln!(self.output).map_err(|_| ()))
+            .is_err()
+        {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "SARIF report",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Derive a SARIF rule identifier from a `Rule`'s `id`, which is used
+/// verbatim when it is already kebab-case. `Rule`s that do not supply an
+/// `id` get a slug of their description,
+/// e.g. "should-have-a-license-file-in-the-project-directory"
+fn rule_id(rule: &Rule) -> String {
+    let mut id = String::with_capacity(rule.id().len());
+    for c in rule.id().chars() {
+        if c.is_alphanumeric() {
+            id.extend(c.to_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    while id.ends_with('-') {
+        id.pop();
+    }
+    id
+}
+
+fn path_to_file_uri(path: &Path) -> String {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    if raw.starts_with('/') {
+        format!("file://{}", encode_uri_path(&raw))
+    } else {
+        format!("file:///{}", encode_uri_path(&raw))
+    }
+}
+
+fn encode_uri_path(raw: &str) -> String {
+    let mut encoded = String::with_capacity(raw.len());
+    for b in raw.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use tempfile::tempdir;
+    use HasLicenseFile;
+
+    fn report(manifest: &Path, rules: &[&FixedRule]) -> Value {
+        let mut reporter = SarifReporter::new(Vec::new(), manifest);
+        let rules_and_details: Vec<(&FixedRule, &str)> = rules.iter().map(|r| (*r, "")).collect();
+        simulate_run(&mut reporter, &rules_and_details);
+        serde_json::from_slice(&reporter.into_inner()).expect("Output should be well-formed JSON")
+    }
+
+    #[test]
+    fn sarif_describes_all_rules_and_reports_non_successes() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let pass = FixedRule("Should pass.", RuleOutcome::Success);
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let unknown = FixedRule("Should be unknown.", RuleOutcome::Undetermined);
+        let log = report(&dir.path().join("Cargo.toml"), &[&pass, &fail, &unknown]);
+        assert_eq!("2.1.0", log["version"]);
+        let run = &log["runs"][0];
+        assert_eq!(3, run["tool"]["driver"]["rules"].as_array().unwrap().len());
+        assert_eq!("should-pass", run["tool"]["driver"]["rules"][0]["id"]);
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(2, results.len());
+        assert_eq!("should-fail", results[0]["ruleId"]);
+        assert_eq!(1, results[0]["ruleIndex"]);
+        assert_eq!("error", results[0]["level"]);
+        assert_eq!("warning", results[1]["level"]);
+        assert_eq!(
+            "Cargo.toml",
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+        );
+        let base_uri = run["originalUriBaseIds"][PROJECT_ROOT_BASE_ID]["uri"]
+            .as_str()
+            .unwrap();
+        assert!(base_uri.starts_with("file:///"));
+        assert!(base_uri.ends_with('/'));
+    }
+
+    #[test]
+    fn sarif_lowers_the_level_of_advisory_rules() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let unknown = FixedRule("Should be unknown.", RuleOutcome::Undetermined);
+        let mut reporter = SarifReporter::new(Vec::new(), dir.path().join("Cargo.toml"));
+        let reports: Vec<(&FixedRule, RuleReport)> = vec![
+            (&fail, Severity::Warning),
+            (&unknown, Severity::Warning),
+            (&fail, Severity::Info),
+        ].into_iter()
+            .map(|(rule, severity)| {
+                let mut report = fixed_report(rule, "");
+                report.severity = severity;
+                (rule, report)
+            })
+            .collect();
+        simulate_run_with_reports(&mut reporter, &reports);
+        let log: Value = serde_json::from_slice(&reporter.into_inner())
+            .expect("Output should be well-formed JSON");
+        let levels: Vec<&Value> = log["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| &r["level"])
+            .collect();
+        assert_eq!(vec!["warning", "note", "note"], levels);
+    }
+
+    #[test]
+    fn sarif_locates_findings_at_their_files() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let manifest = dir.path().join("Cargo.toml");
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "");
+        report.findings.push(
+            Finding::new("unused variable")
+                .with_file(dir.path().join("src").join("main.rs"))
+                .with_line(7),
+        );
+        report.findings.push(Finding::new("elsewhere").with_file("/not/in/project.rs"));
+        let mut reporter = SarifReporter::new(Vec::new(), &manifest);
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let log: Value = serde_json::from_slice(&reporter.into_inner())
+            .expect("Output should be well-formed JSON");
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(2, results.len());
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!("src/main.rs", location["artifactLocation"]["uri"]);
+        assert_eq!(PROJECT_ROOT_BASE_ID, location["artifactLocation"]["uriBaseId"]);
+        assert_eq!(7, location["region"]["startLine"]);
+        assert_eq!("Should fail.: unused variable", results[0]["message"]["text"]);
+        let elsewhere = &results[1]["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!("file:///not/in/project.rs", elsewhere["uri"]);
+        assert!(elsewhere["uriBaseId"].is_null());
+    }
+
+    #[test]
+    fn rule_ids_are_kebab_case_slugs() {
+        let rule = FixedRule(
+            "Should `cargo clean` and `cargo build` without any warnings or errors.",
+            RuleOutcome::Success,
+        );
+        assert_eq!(
+            "should-cargo-clean-and-cargo-build-without-any-warnings-or-errors",
+            rule_id(&rule)
+        );
+    }
+
+    #[test]
+    fn rule_ids_prefer_the_rule_id() {
+        assert_eq!("has-license-file", rule_id(&HasLicenseFile::default()));
+    }
+
+    #[test]
+    fn uri_paths_are_percent_encoded() {
+        assert_eq!("/a%20b/Cargo.toml", encode_uri_path("/a b/Cargo.toml"));
+        assert_eq!("file:///tmp/x", path_to_file_uri(Path::new("/tmp/x")));
+    }
+}
@@ -0,0 +1,223 @@
+//! TAP (Test Anything Protocol) reporting of `Rule` evaluation results.
+//!
+//! Each `Rule` is represented as a TAP test point named by its description.
+use super::super::{CheckError, CultureReport, OutcomeStats, Rule, RuleOutcome, RuleReport};
+use super::{advisory_severity, Reporter};
+use serde_json;
+use std::io::Write;
+
+/// `Reporter` that prints a TAP version 13 stream as the run progresses.
+///
+/// The plan line is printed before any `Rule` is evaluated, followed by an
+/// `ok` or `not ok` test point for each `Rule`. `RuleOutcome::Undetermined`
+/// and `RuleOutcome::Skipped` are represented with a `# SKIP` directive,
+/// and advisory `Rule`s that were not upheld with a `# TODO` directive, so
+/// that none of them fails the stream. Verbose details and
+/// non-successful outcomes are described in a YAML diagnostics block
+/// following the relevant test point.
+#[derive(Debug)]
+pub struct TapReporter<W: Write> {
+    output: W,
+    test_number: usize,
+}
+
+impl<W: Write> TapReporter<W> {
+    /// Create a `TapReporter` that prints to the supplied `Write` instance.
+    pub fn new(output: W) -> Self {
+        TapReporter {
+            output,
+            test_number: 0,
+        }
+    }
+
+    /// Unwrap this `TapReporter`, returning the underlying `Write` instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+
+    fn write_test_point(&mut self, rule: &Rule, report: &RuleReport) -> ::std::io::Result<()> {
+        let outcome = &report.outcome;
+        let details = report.details.as_str();
+        let description = rule.description().replace('#', "\\#");
+        match (outcome, advisory_severity(report)) {
+            (RuleOutcome::Success, _) => {
+                writeln!(self.output, "ok {} - {}", self.test_number, description)?
+            }
+            (_, Some(severity)) => writeln!(
+                self.output,
+                "not ok {} - {} # TODO advisory rule of {} severity",
+                self.test_number, description, severity
+            )?,
+            (RuleOutcome::Failure, None) => {
+                writeln!(self.output, "not ok {} - {}", self.test_number, description)?
+            }
+            (RuleOutcome::Undetermined, None) => writeln!(
+                self.output,
+                "ok {} - {} # SKIP outcome undetermined",
+                self.test_number, description
+            )?,
+            (RuleOutcome::Skipped, _) => writeln!(
+                self.output,
+                "ok {} - {} # SKIP prerequisite not upheld",
+                self.test_number, description
+            )?,
+        }
+        if *outcome == RuleOutcome::Success
+            && details.trim().is_empty()
+            && report.findings.is_empty()
+        {
+            return self.output.flush();
+        }
+        writeln!(self.output, "  ---")?;
+        writeln!(self.output, "  outcome: {}", outcome_name(outcome))?;
+        writeln!(self.output, "  severity: {}", report.severity)?;
+        if !report.findings.is_empty() {
+            writeln!(self.output, "  findings:")?;
+            for finding in &report.findings {
+                // A JSON string is also a valid YAML double-quoted scalar
+                let quoted = serde_json::to_string(&finding.to_string())
+                    .unwrap_or_else(|_| String::from("\"\""));
+                writeln!(self.output, "    - {}", quoted)?;
+            }
+        }
+        if !details.trim().is_empty() {
+            writeln!(self.output, "  details: |")?;
+            for line in details.trim_matches('\n').lines() {
+                writeln!(self.output, "    {}", line)?;
+            }
+        }
+        writeln!(self.output, "  ...")?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Reporter for TapReporter<W> {
+    fn run_started(&mut self, rules: &[&Rule]) -> Result<(), CheckError> {
+        self.test_number = 0;
+        if writeln!(self.output, "TAP version 13")
+            .and_then(|_| writeln!(self.output, "1..{}", rules.len()))
+            .is_err()
+        {
+            return Err(CheckError::PrintOutputFailure { topic: "TAP plan" });
+        }
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, message: &str) -> Result<(), CheckError> {
+        for line in message.lines() {
+            if writeln!(self.output, "# {}", line).is_err() {
+                return Err(CheckError::PrintOutputFailure {
+                    topic: "diagnostic message",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn rule_started(&mut self, _rule: &Rule) -> Result<(), CheckError> {
+        self.test_number += 1;
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        if self.write_test_point(rule, report).is_err() {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "TAP test point",
+            });
+        }
+        Ok(())
+    }
+
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError> {
+        let stats = OutcomeStats::from(report);
+        if writeln!(
+            self.output,
+            "# culture result: {} passed. {} failed. {} undetermined.",
+            stats.success_count, stats.fail_count, stats.undetermined_count
+        ).is_err()
+        {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "culture check summary",
+            });
+        }
+        Ok(())
+    }
+}
+
+fn outcome_name(outcome: &RuleOutcome) -> &'static str {
+    match *outcome {
+        RuleOutcome::Success => "success",
+        RuleOutcome::Failure => "failure",
+        RuleOutcome::Undetermined => "undetermined",
+        RuleOutcome::Skipped => "skipped",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use {Finding, Severity};
+
+    fn report(rules_and_details: &[(&FixedRule, &str)]) -> String {
+        let mut reporter = TapReporter::new(Vec::new());
+        simulate_run(&mut reporter, rules_and_details);
+        String::from_utf8(reporter.into_inner()).expect("Output should be utf8")
+    }
+
+    #[test]
+    fn tap_plan_and_test_points() {
+        let pass = FixedRule("Should pass.", RuleOutcome::Success);
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let unknown = FixedRule("Should be unknown.", RuleOutcome::Undetermined);
+        let tap = report(&[(&pass, ""), (&fail, ""), (&unknown, "")]);
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!("TAP version 13", lines[0]);
+        assert_eq!("1..3", lines[1]);
+        assert_eq!("ok 1 - Should pass.", lines[2]);
+        assert_eq!("not ok 2 - Should fail.", lines[3]);
+        assert!(tap.contains("ok 3 - Should be unknown. # SKIP"));
+    }
+
+    #[test]
+    fn tap_advisory_failures_are_todo() {
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "");
+        report.severity = Severity::Warning;
+        let mut reporter = TapReporter::new(Vec::new());
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let tap = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(tap.contains("not ok 1 - Should fail. # TODO advisory rule of warning severity\n"));
+        assert!(tap.contains("  severity: warning\n"));
+    }
+
+    #[test]
+    fn tap_details_go_into_yaml_block() {
+        let fail = FixedRule("Should fail # loudly.", RuleOutcome::Failure);
+        let tap = report(&[(&fail, "first line\nsecond line\n")]);
+        assert!(tap.contains("not ok 1 - Should fail \\# loudly.\n"));
+        assert!(tap.contains(
+            "  ---\n  outcome: failure\n  severity: error\n  details: |\n    first line\n    \
+             second line\n  ...\n"
+        ));
+    }
+
+    #[test]
+    fn tap_findings_go_into_yaml_block() {
+        let fail = FixedRule("Should fail.", RuleOutcome::Failure);
+        let mut report = fixed_report(&fail, "");
+        report
+            .findings
+            .push(Finding::new("Missing \"thing\"").with_file("a.rs").with_line(3));
+        let mut reporter = TapReporter::new(Vec::new());
+        simulate_run_with_reports(&mut reporter, &[(&fail, report)]);
+        let tap = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(tap.contains("  findings:\n    - \"a.rs:3: Missing \\\"thing\\\"\"\n"));
+    }
+
+    #[test]
+    fn tap_empty_plan_for_no_rules() {
+        let tap = report(&[]);
+        assert!(tap.starts_with("TAP version 13\n1..0\n"));
+    }
+}
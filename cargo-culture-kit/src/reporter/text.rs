@@ -0,0 +1,272 @@
+use super::super::{
+    CheckError, CultureReport, IsSuccess, OutcomeStats, Rule, RuleOutcome, RuleReport, Severity,
+};
+use super::{advisory_severity, advisory_summary, Reporter};
+use colored::*;
+use std::io::Write;
+
+/// The default `Reporter`, which prints colored human-oriented prose.
+///
+/// Each `Rule` description is printed as evaluation begins, followed by any
+/// verbose details and a short summary of the outcome, which notes the
+/// `Severity` of advisory `Rule`s that were not upheld. The `Finding`s of a
+/// `Rule` that was not upheld are listed beneath its outcome, which for a
+/// skipped `Rule` names the prerequisite that was the root cause. A single
+/// line of aggregate statistics is printed at the end of the run.
+#[derive(Debug)]
+pub struct TextReporter<W: Write> {
+    output: W,
+}
+
+impl<W: Write> TextReporter<W> {
+    /// Create a `TextReporter` that prints to the supplied `Write` instance.
+    /// `std::io::stdout()` is a common choice for non-test applications.
+    pub fn new(output: W) -> Self {
+        TextReporter { output }
+    }
+
+    /// Unwrap this `TextReporter`, returning the underlying `Write` instance.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
+
+impl<W: Write> Reporter for TextReporter<W> {
+    fn run_started(&mut self, _rules: &[&Rule]) -> Result<(), CheckError> {
+        Ok(())
+    }
+
+    fn diagnostic(&mut self, message: &str) -> Result<(), CheckError> {
+        if writeln!(self.output, "{}", message).is_err() {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "diagnostic message",
+            });
+        }
+        Ok(())
+    }
+
+    fn rule_started(&mut self, rule: &Rule) -> Result<(), CheckError> {
+        if self.output
+            .write_all(rule.description().as_bytes())
+            .and_then(|_| self.output.flush())
+            .is_err()
+        {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "rule description",
+            });
+        }
+        Ok(())
+    }
+
+    fn rule_finished(&mut self, rule: &Rule, report: &RuleReport) -> Result<(), CheckError> {
+        if self.output
+            .write_all(report.details.as_bytes())
+            .and_then(|_| writeln!(self.output, " ... {}", summary_str(report)))
+            .is_err()
+        {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "rule evaluation outcome",
+            });
+        }
+        if report.outcome != RuleOutcome::Success {
+            for finding in &report.findings {
+                if writeln!(self.output, "    {}", finding).is_err() {
+                    return Err(CheckError::PrintOutputFailure {
+                        topic: "rule finding",
+                    });
+                }
+            }
+        }
+        if report.outcome == RuleOutcome::Failure {
+            if let Some(remediation) = rule.remediation() {
+                if writeln!(self.output, "    {} {}", "help:".yellow(), remediation).is_err() {
+                    return Err(CheckError::PrintOutputFailure {
+                        topic: "rule remediation",
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_finished(&mut self, report: &CultureReport) -> Result<(), CheckError> {
+        let outcome_stats = OutcomeStats::from(report);
+        let conclusion = if outcome_stats.is_success() {
+            "ok".green()
+        } else {
+            "FAILED".red()
+        };
+        if writeln!(
+            self.output,
+            "culture result: {}. {} passed. {} failed. {} undetermined.{}",
+            conclusion,
+            outcome_stats.success_count,
+            outcome_stats.fail_count,
+            outcome_stats.undetermined_count,
+            advisory_summary(&outcome_stats)
+        ).is_err()
+        {
+            return Err(CheckError::PrintOutputFailure {
+                topic: "culture check summary",
+            });
+        };
+        Ok(())
+    }
+}
+
+fn summary_str(report: &RuleReport) -> String {
+    if report.cached {
+        format!("{} (cached)", outcome_str(report))
+    } else {
+        outcome_str(report).to_string()
+    }
+}
+
+fn outcome_str(report: &RuleReport) -> ColoredString {
+    let label = match report.outcome {
+        RuleOutcome::Success => return "ok".green(),
+        RuleOutcome::Failure => "FAILED",
+        RuleOutcome::Undetermined => "UNDETERMINED",
+        RuleOutcome::Skipped => return "SKIPPED".normal(),
+    };
+    match advisory_severity(report) {
+        None => label.red(),
+        Some(Severity::Warning) => format!("{} (warning)", label).yellow(),
+        Some(severity) => format!("{} ({})", label, severity).normal(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{rule_report, FixedRule};
+    use super::*;
+    use {Finding, HasLicenseFile, HasRustfmtFile};
+
+    #[test]
+    fn text_reporter_prints_description_details_and_outcome_in_order() {
+        let rule = HasLicenseFile::default();
+        let rule_report = RuleReport {
+            details: "\nno luck\n".to_owned(),
+            ..rule_report(&rule, RuleOutcome::Failure)
+        };
+        let mut reporter = TextReporter::new(Vec::new());
+        reporter
+            .rule_started(&rule)
+            .and_then(|_| reporter.rule_finished(&rule, &rule_report))
+            .expect("Writing to a Vec should not fail");
+        let printed = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        let description_at = printed.find(rule.description()).expect("Missing description");
+        let details_at = printed.find("no luck").expect("Missing details");
+        let outcome_at = printed.find("FAILED").expect("Missing outcome");
+        assert!(description_at < details_at);
+        assert!(details_at < outcome_at);
+    }
+
+    #[test]
+    fn text_reporter_marks_cached_outcomes() {
+        let rule = HasLicenseFile::default();
+        let rule_report = RuleReport {
+            cached: true,
+            ..rule_report(&rule, RuleOutcome::Success)
+        };
+        let mut reporter = TextReporter::new(Vec::new());
+        reporter
+            .rule_finished(&rule, &rule_report)
+            .expect("Writing to a Vec should not fail");
+        let printed = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(printed.contains("(cached)"));
+    }
+
+    #[test]
+    fn text_reporter_prints_remediation_only_for_failures() {
+        let rule = HasRustfmtFile::default();
+        let remediation = rule.remediation().expect("Should have a remediation");
+        for &(ref outcome, expected) in &[
+            (RuleOutcome::Success, false),
+            (RuleOutcome::Undetermined, false),
+            (RuleOutcome::Failure, true),
+        ] {
+            let rule_report = rule_report(&rule, outcome.clone());
+            let mut reporter = TextReporter::new(Vec::new());
+            reporter
+                .rule_finished(&rule, &rule_report)
+                .expect("Writing to a Vec should not fail");
+            let printed =
+                String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+            assert_eq!(expected, printed.contains(remediation));
+        }
+    }
+
+    #[test]
+    fn text_reporter_prints_findings_only_when_not_upheld() {
+        let rule = HasLicenseFile::default();
+        for &(ref outcome, expected) in &[
+            (RuleOutcome::Success, false),
+            (RuleOutcome::Undetermined, true),
+            (RuleOutcome::Failure, true),
+            (RuleOutcome::Skipped, true),
+        ] {
+            let rule_report = RuleReport {
+                findings: vec![
+                    Finding::new("unused variable")
+                        .with_file("src/lib.rs")
+                        .with_line(7),
+                ],
+                ..rule_report(&rule, outcome.clone())
+            };
+            let mut reporter = TextReporter::new(Vec::new());
+            reporter
+                .rule_finished(&rule, &rule_report)
+                .expect("Writing to a Vec should not fail");
+            let printed =
+                String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+            assert_eq!(expected, printed.contains("src/lib.rs:7: unused variable"));
+        }
+    }
+
+    #[test]
+    fn text_reporter_summarizes_stats() {
+        let report = CultureReport {
+            rules: vec![
+                rule_report(&FixedRule("A", RuleOutcome::Success), RuleOutcome::Success),
+                rule_report(
+                    &FixedRule("B", RuleOutcome::Undetermined),
+                    RuleOutcome::Undetermined,
+                ),
+            ],
+        };
+        let mut reporter = TextReporter::new(Vec::new());
+        reporter
+            .run_finished(&report)
+            .expect("Writing to a Vec should not fail");
+        let printed = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(printed.starts_with("culture result: "));
+        assert!(printed.contains("1 passed. 0 failed. 1 undetermined."));
+        assert!(!printed.contains("warnings"));
+    }
+
+    #[test]
+    fn text_reporter_notes_advisory_severities() {
+        let rule = HasRustfmtFile::default();
+        let advisory = |severity| RuleReport {
+            severity,
+            ..rule_report(&rule, RuleOutcome::Failure)
+        };
+        let report = CultureReport {
+            rules: vec![advisory(Severity::Warning), advisory(Severity::Info)],
+        };
+        let mut reporter = TextReporter::new(Vec::new());
+        for rule_report in &report {
+            reporter
+                .rule_finished(&rule, rule_report)
+                .expect("Writing to a Vec should not fail");
+        }
+        reporter
+            .run_finished(&report)
+            .expect("Writing to a Vec should not fail");
+        let printed = String::from_utf8(reporter.into_inner()).expect("Output should be utf8");
+        assert!(printed.contains("FAILED (warning)"));
+        assert!(printed.contains("FAILED (info)"));
+        assert!(printed.contains("0 passed. 0 failed. 0 undetermined. 1 warnings. 1 info."));
+    }
+}
@@ -1,10 +1,14 @@
-use super::{Rule, RuleContext, RuleOutcome};
+use super::super::config::ConfigError;
+use super::super::process::{self, RunError};
+use super::{build_inputs, culture_target_dir, Finding, Rule, RuleContext, RuleCost, RuleOutcome};
 use cargo_metadata::Metadata;
-use regex::Regex;
+use glob::Pattern;
+use serde_json;
+use std::collections::BTreeMap;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::str::from_utf8;
+use std::time::Instant;
 
 /// Rule that asserts a good Rust project:
 /// "Should `cargo clean` and `cargo build` without any warnings or errors."
@@ -18,39 +22,160 @@ use std::str::from_utf8;
 ///
 /// While not every warning is appropriate to be absent from every project,
 /// developers have the ability to thoughtfully silence warnings that are not
-/// relevant to the present use case.
+/// relevant to the present use case. Lints that a project has decided to
+/// tolerate can also be allowed with `with_allowed_lints`, as can the
+/// warnings about files outside the project's control, such as generated
+/// code, with `with_allowed_paths`. A project that cannot yet be rid of all
+/// of its warnings may instead gate on regressions by setting a budget with
+/// `with_maximum_warnings`.
+/// Warnings about packages outside the workspace, such as path dependencies
+/// kept elsewhere, are not held against the project.
+///
+/// Each warning or error is reported as a `Finding` with its lint code,
+/// file and line, and verbose output includes the compiler's rendering of
+/// each, along with the number of warnings of each package.
 ///
 /// # Caveats
 ///
 /// Though this rule makes an effort to avoid needless work by targeting
 /// the `cargo clean` invocations to the project's own packages,
 /// unless dependencies have been previously built, `evaluate` is likely
-/// to take a while.
+/// to take a while. The project is built in a `culture` directory within
+/// its target directory, shared with `PassesMultipleTests`, so that the
+/// developer's own build artifacts are left alone.
 #[derive(Debug, Default)]
-pub struct BuildsCleanlyWithoutWarningsOrErrors;
+pub struct BuildsCleanlyWithoutWarningsOrErrors {
+    allowed_lints: Vec<String>,
+    allowed_paths: Vec<Pattern>,
+    maximum_warnings: Option<usize>,
+}
+
+impl BuildsCleanlyWithoutWarningsOrErrors {
+    /// Also tolerate warnings from the named lints, such as `dead_code` or
+    /// `clippy::needless_return`.
+    pub fn with_allowed_lints(mut self, allowed_lints: Vec<String>) -> Self {
+        self.allowed_lints.extend(allowed_lints);
+        self
+    }
+
+    /// Also tolerate warnings about files whose paths, relative to the
+    /// workspace root, match one of the `globs`, such as `src/generated/**`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidRuleOption` when one of the `globs` is
+    /// not a valid glob pattern.
+    pub fn with_allowed_paths<S: AsRef<str>>(mut self, globs: &[S]) -> Result<Self, ConfigError> {
+        for glob in globs {
+            let pattern = Pattern::new(glob.as_ref()).map_err(|e| ConfigError::InvalidRuleOption {
+                rule_id: String::from("builds-cleanly"),
+                message: format!("\"{}\" is not a valid glob pattern: {}", glob.as_ref(), e),
+            })?;
+            self.allowed_paths.push(pattern);
+        }
+        Ok(self)
+    }
+
+    /// Uphold the `Rule` as long as there are no more than `maximum`
+    /// warnings that are not otherwise tolerated. Errors always fail it.
+    pub fn with_maximum_warnings(mut self, maximum: usize) -> Self {
+        self.maximum_warnings = Some(maximum);
+        self
+    }
+
+    /// Whether `diagnostic` is a warning from one of the `allowed_lints`, or
+    /// about a file within the `allowed_paths`
+    fn tolerates(&self, diagnostic: &CompilerDiagnostic, workspace_root: Option<&Path>) -> bool {
+        if !diagnostic.is_warning() {
+            return false;
+        }
+        if self.allowed_lints.iter().any(|a| diagnostic.has_lint(a)) {
+            return true;
+        }
+        match diagnostic.file {
+            Some(ref file) => {
+                let relative = workspace_root
+                    .and_then(|root| file.strip_prefix(root).ok())
+                    .unwrap_or(file);
+                self.allowed_paths.iter().any(|p| p.matches_path(relative))
+            }
+            None => false,
+        }
+    }
+}
 
 impl Rule for BuildsCleanlyWithoutWarningsOrErrors {
+    fn id(&self) -> &str {
+        "builds-cleanly"
+    }
+
     fn description(&self) -> &'static str {
         "Should `cargo clean` and `cargo build` without any warnings or errors."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "A project striving for excellence and accessibility should build out of \
+             the box with common tooling, without superfluous warnings or errors. \
+             Warnings that are not relevant can be thoughtfully silenced.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some(
+            "Run `cargo build` and fix the reported warnings and errors, or silence \
+             irrelevant warnings with targeted `#[allow(...)]` attributes.",
+        )
+    }
+
+    fn cost(&self) -> RuleCost {
+        RuleCost::Expensive
+    }
+
+    fn prerequisites(&self) -> Vec<&str> {
+        vec!["cargo-metadata-readable"]
+    }
+
+    fn inputs(&self) -> Vec<PathBuf> {
+        build_inputs()
+    }
+
+    fn configuration(&self) -> String {
+        let allowed_paths: Vec<&str> = self.allowed_paths.iter().map(Pattern::as_str).collect();
+        json!({
+            "allowed-lints": self.allowed_lints,
+            "allowed-paths": allowed_paths,
+            "maximum-warnings": self.maximum_warnings,
+        })
+        .to_string()
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
         let cargo = get_cargo_command();
         let RuleContext {
             cargo_manifest_file_path,
             verbose,
             metadata,
+            deadline,
             print_output,
+            findings,
         } = context;
-        let packages_cleaned = clean_packages(
+        let target_dir = culture_target_dir(cargo_manifest_file_path, metadata);
+        match clean_packages(
             &cargo,
             cargo_manifest_file_path,
             verbose,
             metadata,
+            &target_dir,
+            deadline,
             print_output,
-        );
-        if !packages_cleaned {
-            return RuleOutcome::Failure;
+        ) {
+            Ok(true) => (),
+            Ok(false) => return RuleOutcome::Failure,
+            Err(e) => {
+                findings.push(Finding::new(format!("`cargo clean` {}", e)));
+                return RuleOutcome::Undetermined;
+            }
         }
         let mut build_cmd = Command::new(&cargo);
         build_cmd.arg("build");
@@ -58,65 +183,243 @@ impl Rule for BuildsCleanlyWithoutWarningsOrErrors {
             .arg("--manifest-path")
             .arg(cargo_manifest_file_path);
         build_cmd.arg("--message-format=json");
+        build_cmd.env("CARGO_TARGET_DIR", &target_dir);
         let command_str = format!("{:?}", build_cmd);
-        let build_output = match build_cmd.output() {
+        let build_output = match process::run(&mut build_cmd, None, deadline) {
             Ok(o) => o,
-            Err(_e) => {
+            Err(e) => {
+                findings.push(Finding::new(format!("`cargo build` {}", e)));
                 return RuleOutcome::Undetermined;
             }
         };
+        let workspace_root = metadata
+            .as_ref()
+            .map(|m| PathBuf::from(&m.workspace_root));
+        let diagnostics: Vec<CompilerDiagnostic> =
+            workspace_diagnostics(&String::from_utf8_lossy(&build_output.stdout), metadata)
+                .into_iter()
+                .filter(|d| !self.tolerates(d, workspace_root.as_deref()))
+                .collect();
         if !build_output.status.success() {
+            findings.extend(diagnostics.iter().map(CompilerDiagnostic::finding));
             if verbose {
                 let _ = writeln!(print_output, "Build command `{}` failed", command_str);
-                if let Ok(s) = String::from_utf8(build_output.stdout) {
-                    let _ = writeln!(print_output, "`{}` StdOut:\n{}\n\n", command_str, s);
-                }
-                if let Ok(s) = String::from_utf8(build_output.stderr) {
-                    let _ = writeln!(print_output, "`{}` StdErr:\n{}\n\n", command_str, s);
-                }
+                print_diagnostics(print_output, &diagnostics);
+                let _ = writeln!(
+                    print_output,
+                    "`{}` StdErr:\n{}\n\n",
+                    command_str,
+                    String::from_utf8_lossy(&build_output.stderr)
+                );
             }
             return RuleOutcome::Failure;
         }
-        let stdout = match from_utf8(&build_output.stdout) {
-            Ok(stdout) => stdout,
-            Err(e) => {
+        let warnings: Vec<&CompilerDiagnostic> =
+            diagnostics.iter().filter(|d| d.is_warning()).collect();
+        if warnings.is_empty() {
+            return RuleOutcome::Success;
+        }
+        if verbose {
+            let _ = writeln!(print_output, "Found warnings while building the project:");
+            print_diagnostics(print_output, &diagnostics);
+            for (package, count) in warnings_per_package(&warnings) {
+                let _ = writeln!(print_output, "{}: {} warning(s)", package, count);
+            }
+        }
+        match self.maximum_warnings {
+            Some(maximum) if warnings.len() <= maximum => {
                 if verbose {
                     let _ = writeln!(
                         print_output,
-                        "Reading stdout for command `{}` failed : {}",
-                        command_str, e
+                        "{} warning(s) are within the budget of {}",
+                        warnings.len(),
+                        maximum
                     );
                 }
-                return RuleOutcome::Undetermined;
+                RuleOutcome::Success
             }
-        };
-
-        if WARNING_JSON.is_match(stdout) {
-            if verbose {
-                let _ = writeln!(
-                    print_output,
-                    "Found warnings in the cargo build command output:\n{}\n\n",
-                    stdout
-                );
+            maximum => {
+                findings.extend(warnings.iter().map(|w| w.finding()));
+                if let Some(maximum) = maximum {
+                    findings.push(Finding::new(format!(
+                        "{} warning(s) exceed the budget of {}",
+                        warnings.len(),
+                        maximum
+                    )));
+                }
+                RuleOutcome::Failure
             }
-            return RuleOutcome::Failure;
         }
-        RuleOutcome::Success
     }
 }
 
-lazy_static! {
-    static ref WARNING_JSON: Regex = Regex::new(".*\"level\":\"warning\".*")
-        .expect("Failed to create BuildsCleanlyWithoutWarningsOrErrors regex.");
+/// A line of the output of a cargo command run with `--message-format=json`
+#[derive(Debug, Deserialize)]
+struct CargoMessage {
+    reason: String,
+    #[serde(default)]
+    package_id: Option<String>,
+    #[serde(default)]
+    message: Option<CompilerMessage>,
+}
+
+/// The `message` of a `compiler-message` line of cargo output
+#[derive(Debug, Deserialize)]
+struct CompilerMessage {
+    level: String,
+    message: String,
+    #[serde(default)]
+    code: Option<CompilerMessageCode>,
+    #[serde(default)]
+    spans: Vec<CompilerMessageSpan>,
+    #[serde(default)]
+    rendered: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerMessageCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompilerMessageSpan {
+    file_name: String,
+    line_start: u64,
+    is_primary: bool,
+}
+
+/// A warning or error reported by the compiler while building the project
+#[derive(Clone, Debug, PartialEq)]
+struct CompilerDiagnostic {
+    /// The name of the package being built, when known
+    package: Option<String>,
+    level: String,
+    /// The lint or error code, such as `dead_code` or `E0425`
+    code: Option<String>,
+    message: String,
+    file: Option<PathBuf>,
+    line: Option<u64>,
+    /// The diagnostic as rustc would print it
+    rendered: Option<String>,
+}
+
+impl CompilerDiagnostic {
+    fn is_warning(&self) -> bool {
+        self.level == "warning"
+    }
+
+    fn has_lint(&self, lint: &str) -> bool {
+        self.code.as_ref().is_some_and(|code| code == lint)
+    }
+
+    fn finding(&self) -> Finding {
+        let mut finding = Finding::new(match self.code {
+            Some(ref code) => format!("{}[{}]: {}", self.level, code, self.message),
+            None => format!("{}: {}", self.level, self.message),
+        });
+        if let Some(ref file) = self.file {
+            finding = finding.with_file(file.clone());
+        }
+        if let Some(line) = self.line {
+            finding = finding.with_line(line);
+        }
+        finding
+    }
+}
+
+/// Extract the warnings and errors reported in the output of a cargo
+/// command run with `--message-format=json`. Warnings about packages that
+/// are not members of the workspace, such as path dependencies kept
+/// elsewhere, are left out, since the project cannot fix them itself.
+fn workspace_diagnostics(stdout: &str, metadata: &Option<Metadata>) -> Vec<CompilerDiagnostic> {
+    let workspace_root = metadata
+        .as_ref()
+        .map(|m| PathBuf::from(&m.workspace_root));
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|m| m.reason == "compiler-message")
+        .filter_map(|m| {
+            let message = m.message?;
+            let is_warning = message.level == "warning";
+            if !is_warning && !message.level.starts_with("error") {
+                return None;
+            }
+            let package_id = m.package_id.unwrap_or_default();
+            if is_warning && !is_workspace_member(&package_id, metadata) {
+                return None;
+            }
+            let primary_span = message.spans.into_iter().find(|s| s.is_primary);
+            Some(CompilerDiagnostic {
+                package: package_name(&package_id, metadata),
+                level: message.level,
+                code: message.code.map(|c| c.code),
+                message: message.message,
+                file: primary_span.as_ref().map(|s| match workspace_root {
+                    Some(ref root) => root.join(&s.file_name),
+                    None => PathBuf::from(&s.file_name),
+                }),
+                line: primary_span.map(|s| s.line_start),
+                rendered: message.rendered,
+            })
+        })
+        .collect()
+}
+
+/// Whether the package of `package_id` is a member of the workspace, which
+/// is assumed to be so when there is no `metadata` to tell
+fn is_workspace_member(package_id: &str, metadata: &Option<Metadata>) -> bool {
+    match *metadata {
+        None => true,
+        Some(ref m) => m.packages.iter().any(|p| {
+            p.id == package_id
+                && m.workspace_members
+                    .iter()
+                    .any(|w| w.name == p.name && w.version.to_string() == p.version)
+        }),
+    }
+}
+
+fn package_name(package_id: &str, metadata: &Option<Metadata>) -> Option<String> {
+    metadata
+        .as_ref()
+        .and_then(|m| m.packages.iter().find(|p| p.id == package_id))
+        .map(|p| p.name.clone())
 }
 
+/// The number of `warnings` of each package, in order of package name
+fn warnings_per_package(warnings: &[&CompilerDiagnostic]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for warning in warnings {
+        let package = warning
+            .package
+            .clone()
+            .unwrap_or_else(|| String::from("(unknown package)"));
+        *counts.entry(package).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn print_diagnostics(print_output: &mut Write, diagnostics: &[CompilerDiagnostic]) {
+    for diagnostic in diagnostics {
+        let _ = match diagnostic.rendered {
+            Some(ref rendered) => write!(print_output, "{}", rendered),
+            None => writeln!(print_output, "{}", diagnostic.finding()),
+        };
+    }
+}
+
+/// Whether every package of the project was cleaned, or else why the
+/// cleaning was abandoned.
 fn clean_packages(
     cargo_command: &str,
     cargo_manifest_file_path: &Path,
     verbose: bool,
     metadata: &Option<Metadata>,
+    target_dir: &Path,
+    deadline: Option<Instant>,
     print_output: &mut Write,
-) -> bool {
+) -> Result<bool, RunError> {
     match *metadata {
         None => {
             if verbose {
@@ -125,13 +428,13 @@ fn clean_packages(
                     "No metadata to discover which packages to clean."
                 );
             }
-            false
+            Ok(false)
         }
         Some(ref m) if m.packages.is_empty() => {
             if verbose {
                 let _ = writeln!(print_output, "No packages to clean.");
             }
-            false
+            Ok(false)
         }
         Some(ref m) => {
             let mut all_cleaned = true;
@@ -141,14 +444,16 @@ fn clean_packages(
                     &p.name,
                     cargo_manifest_file_path,
                     verbose,
+                    target_dir,
+                    deadline,
                     print_output,
-                );
+                )?;
                 if !cleaned && verbose {
                     let _ = writeln!(print_output, "Could not clean package {} .", &p.name);
                 }
                 all_cleaned = all_cleaned && cleaned;
             }
-            all_cleaned
+            Ok(all_cleaned)
         }
     }
 }
@@ -158,24 +463,28 @@ fn clean_package(
     package_name: &str,
     cargo_manifest_file_path: &Path,
     verbose: bool,
+    target_dir: &Path,
+    deadline: Option<Instant>,
     print_output: &mut Write,
-) -> bool {
-    let mut clean_cmd = Command::new(&cargo_command);
+) -> Result<bool, RunError> {
+    let mut clean_cmd = Command::new(cargo_command);
     clean_cmd.arg("clean");
     clean_cmd
         .arg("--manifest-path")
         .arg(cargo_manifest_file_path);
     clean_cmd.arg("--package").arg(package_name);
-    let clean_output = match clean_cmd.output() {
+    clean_cmd.env("CARGO_TARGET_DIR", target_dir);
+    let clean_output = match process::run(&mut clean_cmd, None, deadline) {
         Ok(o) => o,
-        Err(e) => {
+        Err(RunError::Launch(e)) => {
             if verbose {
                 let _ = writeln!(print_output, "{}", e);
             }
-            return false;
+            return Ok(false);
         }
+        Err(e) => return Err(e),
     };
-    clean_output.status.success()
+    Ok(clean_output.status.success())
 }
 
 fn get_cargo_command() -> String {
@@ -189,6 +498,181 @@ mod tests {
     use std::fs::{create_dir_all, File};
     use tempfile::tempdir;
 
+    const BUILD_STDOUT: &str = concat!(
+        r#"{"reason":"compiler-artifact","package_id":"kid 0.1.0 (path+file:///ws/kid)"}"#,
+        "\n",
+        r#"{"reason":"compiler-message","package_id":"kid 0.1.0 (path+file:///ws/kid)","#,
+        r#""message":{"level":"warning","message":"unused variable: `x`","#,
+        r#""code":{"code":"unused_variables","explanation":null},"#,
+        r#""spans":[{"file_name":"kid/src/main.rs","line_start":3,"is_primary":true}],"#,
+        r#""rendered":"warning: unused variable: `x`\n"}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","package_id":"kid 0.1.0 (path+file:///ws/kid)","#,
+        r#""message":{"level":"note","message":"n","code":null,"spans":[]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","package_id":"far 0.1.0 (path+file:///elsewhere/far)","#,
+        r#""message":{"level":"warning","message":"function `f` is never used","#,
+        r#""code":{"code":"dead_code","explanation":null},"spans":[]}}"#,
+        "\n",
+        r#"{"reason":"compiler-message","package_id":"kid 0.1.0 (path+file:///ws/kid)","#,
+        r#""message":{"level":"error","message":"e","code":null,"spans":[]}}"#,
+        "\n",
+        "Not JSON at all",
+    );
+
+    fn workspace_metadata() -> Option<Metadata> {
+        let package = |name: &str, dir: &str| {
+            format!(
+                concat!(
+                    r#"{{"name":"{0}","version":"0.1.0","id":"{0} 0.1.0 (path+file://{1})","#,
+                    r#""source":null,"dependencies":[],"targets":[],"features":{{}},"#,
+                    r#""manifest_path":"{1}/Cargo.toml"}}"#
+                ),
+                name, dir
+            )
+        };
+        let metadata = format!(
+            r#"{{"packages":[{},{}],"workspace_members":["kid 0.1.0 (path+file:///ws/kid)"],"#,
+            package("kid", "/ws/kid"),
+            package("far", "/elsewhere/far")
+        ) + r#""resolve":null,"workspace_root":"/ws","target_directory":"/ws/target","version":1}"#;
+        Some(serde_json::from_str(&metadata).expect("Metadata should parse"))
+    }
+
+    #[test]
+    fn workspace_diagnostics_locate_warnings_and_errors() {
+        let diagnostics = workspace_diagnostics(BUILD_STDOUT, &None);
+        assert_eq!(
+            vec![
+                Finding::new("warning[unused_variables]: unused variable: `x`")
+                    .with_file("kid/src/main.rs")
+                    .with_line(3),
+                Finding::new("warning[dead_code]: function `f` is never used"),
+                Finding::new("error: e"),
+            ],
+            diagnostics
+                .iter()
+                .map(CompilerDiagnostic::finding)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("warning: unused variable: `x`\n"),
+            diagnostics[0].rendered.as_deref()
+        );
+    }
+
+    #[test]
+    fn workspace_diagnostics_skip_warnings_from_outside_the_workspace() {
+        let diagnostics = workspace_diagnostics(BUILD_STDOUT, &workspace_metadata());
+        assert_eq!(
+            vec![Some("unused_variables"), None],
+            diagnostics
+                .iter()
+                .map(|d| d.code.as_deref())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(PathBuf::from("/ws/kid/src/main.rs")), diagnostics[0].file);
+        let warnings: Vec<&CompilerDiagnostic> =
+            diagnostics.iter().filter(|d| d.is_warning()).collect();
+        assert_eq!(
+            vec![(String::from("kid"), 1)],
+            warnings_per_package(&warnings).into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn allowed_lints_are_tolerated_only_as_warnings() {
+        let rule = BuildsCleanlyWithoutWarningsOrErrors::default()
+            .with_allowed_lints(vec![String::from("dead_code")]);
+        let diagnostics = workspace_diagnostics(BUILD_STDOUT, &None);
+        assert_eq!(
+            vec![false, true, false],
+            diagnostics
+                .iter()
+                .map(|d| rule.tolerates(d, None))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn allowed_paths_are_matched_relative_to_the_workspace_root() {
+        let rule = BuildsCleanlyWithoutWarningsOrErrors::default()
+            .with_allowed_paths(&["kid/src/**"])
+            .expect("Should be valid globs");
+        let diagnostics = workspace_diagnostics(BUILD_STDOUT, &workspace_metadata());
+        assert_eq!(
+            vec![true, false],
+            diagnostics
+                .iter()
+                .map(|d| rule.tolerates(d, Some(Path::new("/ws"))))
+                .collect::<Vec<_>>()
+        );
+        let elsewhere = BuildsCleanlyWithoutWarningsOrErrors::default()
+            .with_allowed_paths(&["src/**"])
+            .expect("Should be valid globs");
+        assert!(!elsewhere.tolerates(&diagnostics[0], Some(Path::new("/ws"))));
+    }
+
+    #[test]
+    fn configuration_tells_apart_differently_configured_rules() {
+        let default = BuildsCleanlyWithoutWarningsOrErrors::default().configuration();
+        let budgeted = BuildsCleanlyWithoutWarningsOrErrors::default()
+            .with_maximum_warnings(3)
+            .configuration();
+        let allowing = BuildsCleanlyWithoutWarningsOrErrors::default()
+            .with_allowed_lints(vec![String::from("dead_code")])
+            .configuration();
+        assert_ne!(default, budgeted);
+        assert_ne!(default, allowing);
+        assert_ne!(budgeted, allowing);
+    }
+
+    #[test]
+    fn invalid_allowed_paths_are_rejected() {
+        match BuildsCleanlyWithoutWarningsOrErrors::default().with_allowed_paths(&["src/***"]) {
+            Err(ConfigError::InvalidRuleOption { ref rule_id, .. }) => {
+                assert_eq!("builds-cleanly", rule_id)
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn builds_cleanly_tolerates_allowed_lints() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), None);
+        write_warningful_src_main_file(dir.path());
+        let allowed_lints = vec![String::from("dead_code")];
+        let rule =
+            BuildsCleanlyWithoutWarningsOrErrors::default().with_allowed_lints(allowed_lints);
+        let VerbosityOutcomes {
+            verbose,
+            not_verbose,
+        } = execute_rule_against_project_dir_all_verbosities(dir.path(), &rule);
+        assert_eq!(RuleOutcome::Success, verbose.outcome);
+        assert_eq!(RuleOutcome::Success, not_verbose.outcome);
+    }
+
+    #[test]
+    fn builds_cleanly_tolerates_warnings_within_budget() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), None);
+        write_warningful_src_main_file(dir.path());
+        let within = BuildsCleanlyWithoutWarningsOrErrors::default().with_maximum_warnings(1);
+        let outcomes = execute_rule_against_project_dir_all_verbosities(dir.path(), &within);
+        assert_eq!(RuleOutcome::Success, outcomes.verbose.outcome);
+        assert_eq!(RuleOutcome::Success, outcomes.not_verbose.outcome);
+        let exceeded = BuildsCleanlyWithoutWarningsOrErrors::default().with_maximum_warnings(0);
+        let outcomes = execute_rule_against_project_dir_all_verbosities(dir.path(), &exceeded);
+        assert_eq!(RuleOutcome::Failure, outcomes.not_verbose.outcome);
+        assert!(
+            outcomes
+                .not_verbose
+                .findings
+                .contains(&Finding::new("1 warning(s) exceed the budget of 0"))
+        );
+    }
+
     #[test]
     fn builds_cleanly_happy_path_flat_project() {
         let dir = tempdir().expect("Failed to make a temp dir");
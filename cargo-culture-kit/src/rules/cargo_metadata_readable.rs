@@ -1,4 +1,4 @@
-use super::{Rule, RuleContext, RuleOutcome};
+use super::{Finding, Rule, RuleContext, RuleOutcome};
 
 /// Rule that asserts a good Rust project:
 /// "Should have a well-formed Cargo.toml file readable by `cargo metadata`"
@@ -12,17 +12,42 @@ use super::{Rule, RuleContext, RuleOutcome};
 pub struct CargoMetadataReadable;
 
 impl Rule for CargoMetadataReadable {
+    fn id(&self) -> &str {
+        "cargo-metadata-readable"
+    }
+
     fn description(&self) -> &'static str {
         "Should have a well-formed Cargo.toml file readable by `cargo metadata`"
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "Cargo is the community-wide standard tool for managing Rust projects. An \
+             invalid or absent Cargo.toml suggests a nonstandard build methodology or \
+             an accident of misconfiguration.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some(
+            "Ensure a valid Cargo.toml exists at the supplied manifest path, and that \
+             `cargo metadata` succeeds there.",
+        )
+    }
+
     /// Due to the layout of `Rule` execution wherein cargo metadata is read
     /// and parsed as part of `check_culture` and then handed off to the
     /// `Rule`s being checked, `evaluate` will declare a success if the
     /// `metadata` parameter is `Some`.
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
         match *context.metadata {
-            None => RuleOutcome::Failure,
+            None => {
+                context.findings.push(
+                    Finding::new("`cargo metadata` could not read the manifest")
+                        .with_file(context.cargo_manifest_file_path),
+                );
+                RuleOutcome::Failure
+            }
             Some(_) => RuleOutcome::Success,
         }
     }
@@ -0,0 +1,228 @@
+use super::{Finding, Rule, RuleContext, RuleOutcome};
+use cargo_metadata::DependencyKind;
+use regex::Regex;
+
+/// Whether the dependencies matched by a `DependencyPolicy` must or must not
+/// be present.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependencyRequirement {
+    /// Each package must have at least one matching dependency
+    Required,
+    /// No package may have any matching dependency
+    Forbidden,
+}
+
+/// Rule that asserts a good Rust project does, or does not, depend on crates
+/// whose names match a regular expression, such as "every member must
+/// dev-depend on `insta`" or "no package may depend on `openssl`".
+///
+/// Like `UsesPropertyBasedTestLibrary`, the dependencies of each package
+/// in the cargo metadata are inspected. Unlike it, the description,
+/// dependency name pattern and kinds are supplied at runtime, typically
+/// from the `dependency-rules` of a `CultureConfig`.
+///
+/// By default dependencies of every `DependencyKind` are considered,
+/// in every package of the workspace.
+#[derive(Debug)]
+pub struct DependencyPolicy {
+    id: String,
+    description: String,
+    requirement: DependencyRequirement,
+    name_regex: Regex,
+    kinds: Vec<DependencyKind>,
+    member_regex: Option<Regex>,
+    justification: Option<String>,
+    remediation: Option<String>,
+}
+
+impl DependencyPolicy {
+    /// Create a `DependencyPolicy` with the given `requirement` for
+    /// dependencies with a name matching `name_regex`.
+    pub fn new<I: Into<String>, D: Into<String>>(
+        id: I,
+        description: D,
+        requirement: DependencyRequirement,
+        name_regex: Regex,
+    ) -> Self {
+        DependencyPolicy {
+            id: id.into(),
+            description: description.into(),
+            requirement,
+            name_regex,
+            kinds: Vec::new(),
+            member_regex: None,
+            justification: None,
+            remediation: None,
+        }
+    }
+
+    /// Only consider dependencies of the supplied `kinds`, or of any kind
+    /// when `kinds` is empty.
+    pub fn with_kinds(mut self, kinds: Vec<DependencyKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    /// Only inspect the packages whose names match `member_regex`.
+    pub fn with_members(mut self, member_regex: Regex) -> Self {
+        self.member_regex = Some(member_regex);
+        self
+    }
+
+    /// Supply the `Rule::justification`.
+    pub fn with_justification<S: Into<String>>(mut self, justification: S) -> Self {
+        self.justification = Some(justification.into());
+        self
+    }
+
+    /// Supply the `Rule::remediation`.
+    pub fn with_remediation<S: Into<String>>(mut self, remediation: S) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    fn kind_matches(&self, kind: DependencyKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+/// The name of a `DependencyKind` as it is written in cargo metadata
+fn kind_name(kind: DependencyKind) -> &'static str {
+    match kind {
+        DependencyKind::Development => "dev",
+        DependencyKind::Build => "build",
+        _ => "normal",
+    }
+}
+
+impl Rule for DependencyPolicy {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn justification(&self) -> Option<&str> {
+        self.justification.as_deref()
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        self.remediation.as_deref()
+    }
+
+    fn prerequisites(&self) -> Vec<&str> {
+        vec!["cargo-metadata-readable"]
+    }
+
+    fn evaluate(&self, context: RuleContext) -> RuleOutcome {
+        let metadata = match *context.metadata {
+            None => return RuleOutcome::Undetermined,
+            Some(ref m) => m,
+        };
+        let packages: Vec<_> = metadata
+            .packages
+            .iter()
+            .filter(|p| {
+                self.member_regex
+                    .as_ref()
+                    .map(|r| r.is_match(&p.name))
+                    .unwrap_or(true)
+            })
+            .collect();
+        if packages.is_empty() {
+            return RuleOutcome::Undetermined;
+        }
+        let mut outcome = RuleOutcome::Success;
+        for package in packages {
+            let mut matching = package
+                .dependencies
+                .iter()
+                .filter(|d| self.kind_matches(d.kind) && self.name_regex.is_match(&d.name))
+                .peekable();
+            match self.requirement {
+                DependencyRequirement::Required => {
+                    if matching.peek().is_none() {
+                        context.findings.push(
+                            Finding::new(format!(
+                                "Package `{}` lacks a dependency with a name matching `{}`",
+                                package.name,
+                                self.name_regex.as_str()
+                            )).with_file(&package.manifest_path),
+                        );
+                        outcome = RuleOutcome::Failure;
+                    }
+                }
+                DependencyRequirement::Forbidden => {
+                    for dependency in matching {
+                        context.findings.push(
+                            Finding::new(format!(
+                                "Package `{}` has a forbidden {} dependency on `{}`",
+                                package.name,
+                                kind_name(dependency.kind),
+                                dependency.name
+                            )).with_file(&package.manifest_path),
+                        );
+                        outcome = RuleOutcome::Failure;
+                    }
+                }
+            }
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use tempfile::tempdir;
+
+    fn proptest_policy(requirement: DependencyRequirement) -> DependencyPolicy {
+        DependencyPolicy::new(
+            "proptest-policy",
+            "Should follow a proptest policy.",
+            requirement,
+            Regex::new("^proptest$").expect("Could not make regex"),
+        )
+    }
+
+    #[test]
+    fn dependency_policy_respects_kinds() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), Some("proptest"));
+        write_clean_src_main_file(dir.path());
+
+        let required = proptest_policy(DependencyRequirement::Required)
+            .with_kinds(vec![DependencyKind::Development]);
+        let capture = execute_rule_against_project_dir(dir.path(), &required, false);
+        assert_eq!(RuleOutcome::Success, capture.outcome);
+
+        let required = required.with_kinds(vec![DependencyKind::Normal]);
+        let capture = execute_rule_against_project_dir(dir.path(), &required, false);
+        assert_eq!(RuleOutcome::Failure, capture.outcome);
+        assert_eq!(1, capture.findings.len());
+
+        let forbidden = proptest_policy(DependencyRequirement::Forbidden);
+        let capture = execute_rule_against_project_dir(dir.path(), &forbidden, false);
+        assert_eq!(RuleOutcome::Failure, capture.outcome);
+        assert!(capture.findings[0].message.contains("forbidden dev dependency"));
+    }
+
+    #[test]
+    fn dependency_policy_without_matching_members_is_undetermined() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), None);
+        write_clean_src_main_file(dir.path());
+        let rule = proptest_policy(DependencyRequirement::Forbidden)
+            .with_members(Regex::new("^service-").expect("Could not make regex"));
+        let VerbosityOutcomes {
+            verbose,
+            not_verbose,
+        } = execute_rule_against_project_dir_all_verbosities(dir.path(), &rule);
+        assert_eq!(RuleOutcome::Undetermined, verbose.outcome);
+        assert_eq!(RuleOutcome::Undetermined, not_verbose.outcome);
+    }
+}
@@ -1,4 +1,9 @@
-use super::super::file::search_manifest_and_workspace_dir_for_nonempty_file_name_match;
+use super::super::file::{
+    file_search_finding, manifest_and_workspace_dirs,
+    search_manifest_and_workspace_dir_for_nonempty_file_name_match,
+};
+use super::super::config::ConfigError;
+use super::super::scaffold::{ProjectInfo, Scaffold};
 use super::{Rule, RuleContext, RuleOutcome};
 use regex::Regex;
 
@@ -12,28 +17,142 @@ use regex::Regex;
 /// accessible for Rust projects.
 ///
 /// See also: https://github.com/japaric/trust
-#[derive(Default, Debug)]
-pub struct HasContinuousIntegrationFile;
+///
+/// By default the configuration files of any of the `PROVIDERS` are accepted.
+/// `HasContinuousIntegrationFile::with_providers` narrows that down.
+#[derive(Debug)]
+pub struct HasContinuousIntegrationFile {
+    providers: Vec<&'static str>,
+    file_name_regex: Regex,
+}
+
+/// The name of each supported continuous integration provider, paired with
+/// a pattern for the stem of its configuration file names.
+const PROVIDER_FILE_STEMS: &[(&str, &str)] = &[
+    ("appveyor", r"appveyor|\.appveyor"),
+    ("drone", r"\.drone"),
+    ("gitlab-ci", r"\.gitlab-ci"),
+    ("travis", r"\.travis"),
+];
+
+impl HasContinuousIntegrationFile {
+    /// The names of the continuous integration providers whose
+    /// configuration files can be recognized
+    pub const PROVIDERS: &'static [&'static str] = &["appveyor", "drone", "gitlab-ci", "travis"];
 
-lazy_static! {
-    static ref HAS_CONTINUOUS_INTEGRATION_FILE: Regex =
-        Regex::new(r"^(?i)(appveyor|\.appveyor|\.drone|\.gitlab-ci|\.travis)\.ya?ml$")
+    /// Create a `HasContinuousIntegrationFile` that only accepts the
+    /// configuration files of the named `providers`, each of which must be
+    /// one of the `PROVIDERS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidRuleOption` when `providers` is empty
+    /// or names an unknown provider.
+    pub fn with_providers<S: AsRef<str>>(providers: &[S]) -> Result<Self, ConfigError> {
+        let invalid = |message: String| ConfigError::InvalidRuleOption {
+            rule_id: String::from("has-continuous-integration-file"),
+            message,
+        };
+        if providers.is_empty() {
+            return Err(invalid(String::from(
+                "at least one provider must be accepted",
+            )));
+        }
+        let mut accepted = Vec::with_capacity(providers.len());
+        let mut stems = Vec::with_capacity(providers.len());
+        for provider in providers {
+            match PROVIDER_FILE_STEMS
+                .iter()
+                .find(|&&(name, _)| name == provider.as_ref())
+            {
+                Some(&(name, stem)) => {
+                    accepted.push(name);
+                    stems.push(stem);
+                }
+                None => {
+                    return Err(invalid(format!(
+                        "unknown provider \"{}\", expected one of: {}",
+                        provider.as_ref(),
+                        Self::PROVIDERS.join(", ")
+                    )))
+                }
+            }
+        }
+        let file_name_regex = Regex::new(&format!(r"^(?i)({})\.ya?ml$", stems.join("|")))
             .expect("Failed to create HasContinuousIntegrationFile regex.");
+        Ok(HasContinuousIntegrationFile {
+            providers: accepted,
+            file_name_regex,
+        })
+    }
 }
 
+impl Default for HasContinuousIntegrationFile {
+    fn default() -> Self {
+        HasContinuousIntegrationFile::with_providers(Self::PROVIDERS)
+            .expect("Every known provider should be accepted")
+    }
+}
+
+const TRAVIS_YML: &str = "\
+language: rust
+rust:
+  - stable
+script:
+  - cargo build --verbose --all
+  - cargo test --verbose --all
+";
+
 impl Rule for HasContinuousIntegrationFile {
+    fn id(&self) -> &str {
+        "has-continuous-integration-file"
+    }
+
     fn description(&self) -> &'static str {
         "Should have a file suggesting the use of a continuous integration system."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "Continuous integration reduces the odds of regressions in project \
+             functionality, and several accessible options are available for Rust \
+             projects.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some(
+            "Add a CI configuration file, such as .travis.yml, appveyor.yml or \
+             .gitlab-ci.yml, at the workspace root.",
+        )
+    }
+
+    fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+        if !self.providers.contains(&"travis") {
+            return None;
+        }
+        Some(Scaffold {
+            path: project.workspace_root.join(".travis.yml"),
+            contents: String::from(TRAVIS_YML),
+        })
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
-        search_manifest_and_workspace_dir_for_nonempty_file_name_match(
-            &HAS_CONTINUOUS_INTEGRATION_FILE,
+        let outcome = search_manifest_and_workspace_dir_for_nonempty_file_name_match(
+            &self.file_name_regex,
             context.cargo_manifest_file_path,
             context.metadata,
-        )
+        );
+        if outcome != RuleOutcome::Success {
+            context.findings.push(file_search_finding(
+                &self.file_name_regex,
+                &manifest_and_workspace_dirs(context.cargo_manifest_file_path, context.metadata),
+            ));
+        }
+        outcome
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::super::test_support::*;
@@ -162,6 +281,24 @@ mod tests {
         assert_eq!(RuleOutcome::Failure, not_verbose.outcome);
     }
 
+    #[test]
+    fn has_continuous_integration_only_accepts_configured_providers() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        File::create(dir.path().join(".travis.yml"))
+            .and_then(|mut f| f.write_all(b"Hello, I am a CI file."))
+            .expect("Could not write CI file");
+        let travis = HasContinuousIntegrationFile::with_providers(&["travis"])
+            .expect("Should accept a known provider");
+        let gitlab = HasContinuousIntegrationFile::with_providers(&["gitlab-ci"])
+            .expect("Should accept a known provider");
+        let travis_outcomes = execute_rule_against_project_dir_all_verbosities(dir.path(), &travis);
+        let gitlab_outcomes = execute_rule_against_project_dir_all_verbosities(dir.path(), &gitlab);
+        assert_eq!(RuleOutcome::Success, travis_outcomes.verbose.outcome);
+        assert_eq!(RuleOutcome::Failure, gitlab_outcomes.verbose.outcome);
+        assert!(HasContinuousIntegrationFile::with_providers(&["jenkins"]).is_err());
+        assert!(HasContinuousIntegrationFile::with_providers::<&str>(&[]).is_err());
+    }
+
     #[test]
     fn has_continuous_integration_no_ci_file_at_all_fails() {
         let dir = tempdir().expect("Failed to make a temp dir");
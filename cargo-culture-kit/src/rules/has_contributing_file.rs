@@ -1,6 +1,8 @@
 use super::super::file::{
-    find_nonempty_child_file, search_manifest_and_workspace_dir_for_nonempty_file_name_match,
+    file_search_finding, find_nonempty_child_file, manifest_and_workspace_dirs,
+    search_manifest_and_workspace_dir_for_nonempty_file_name_match,
 };
+use super::super::scaffold::{ProjectInfo, Scaffold};
 use super::{Rule, RuleContext, RuleOutcome};
 use regex::Regex;
 use std::path::PathBuf;
@@ -22,11 +24,55 @@ lazy_static! {
         Regex::new(r"^(?i)CONTRIBUTING").expect("Failed to create HasContributingFile regex.");
 }
 
+const CONTRIBUTING_MD: &str = "\
+# Contributing to {name}
+
+Thank you for your interest in contributing to {name}!
+
+## Reporting issues
+
+Please describe the problem, the steps to reproduce it, and the behavior you
+expected instead.
+
+## Submitting changes
+
+1. Fork the repository and create a branch for your change.
+2. Make sure `cargo build` and `cargo test` succeed without warnings.
+3. Format the code with `cargo fmt`.
+4. Open a pull request describing the change and its motivation.
+";
+
 impl Rule for HasContributingFile {
+    fn id(&self) -> &str {
+        "has-contributing-file"
+    }
+
     fn description(&self) -> &str {
         "Should have a CONTRIBUTING file in the project directory."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "A CONTRIBUTING file is a starting point for would-be collaborators, and a \
+             gateway to developer-focused guidance that is more targeted than the \
+             general README.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some(
+            "Add a non-empty CONTRIBUTING file at the workspace root or in a .github \
+             directory.",
+        )
+    }
+
+    fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+        Some(Scaffold {
+            path: project.workspace_root.join("CONTRIBUTING.md"),
+            contents: CONTRIBUTING_MD.replace("{name}", &project.name()),
+        })
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
         let initial_outcome = search_manifest_and_workspace_dir_for_nonempty_file_name_match(
             &HAS_CONTRIBUTING_FILE,
@@ -44,15 +90,29 @@ impl Rule for HasContributingFile {
         if find_nonempty_child_file(&HAS_CONTRIBUTING_FILE, &github_dir) == RuleOutcome::Success {
             return RuleOutcome::Success;
         }
-        if let Some(ref metadata) = context.metadata {
+        let mut searched_dirs =
+            manifest_and_workspace_dirs(context.cargo_manifest_file_path, context.metadata);
+        searched_dirs.push(github_dir);
+        let outcome = if let Some(ref metadata) = context.metadata {
             let workspace_github_dir = PathBuf::from(&metadata.workspace_root).join(".github");
-            match find_nonempty_child_file(&HAS_CONTRIBUTING_FILE, &workspace_github_dir) {
+            let workspace_outcome =
+                find_nonempty_child_file(&HAS_CONTRIBUTING_FILE, &workspace_github_dir);
+            if !searched_dirs.contains(&workspace_github_dir) {
+                searched_dirs.push(workspace_github_dir);
+            }
+            match workspace_outcome {
                 RuleOutcome::Success => RuleOutcome::Success,
-                RuleOutcome::Failure | RuleOutcome::Undetermined => initial_outcome,
+                _ => initial_outcome,
             }
         } else {
             initial_outcome
+        };
+        if outcome != RuleOutcome::Success {
+            context
+                .findings
+                .push(file_search_finding(&HAS_CONTRIBUTING_FILE, &searched_dirs));
         }
+        outcome
     }
 }
 
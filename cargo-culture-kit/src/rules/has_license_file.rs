@@ -1,4 +1,8 @@
-use super::super::file::search_manifest_and_workspace_dir_for_nonempty_file_name_match;
+use super::super::file::{
+    file_search_finding, manifest_and_workspace_dirs,
+    search_manifest_and_workspace_dir_for_nonempty_file_name_match,
+};
+use super::super::scaffold::{ProjectInfo, Scaffold};
 use super::{Rule, RuleContext, RuleOutcome};
 use regex::Regex;
 
@@ -17,19 +21,106 @@ lazy_static! {
         Regex::new(r"^(?i)LICENSE").expect("Failed to create HasLicenseFile regex.");
 }
 
+const MIT_LICENSE: &str = "\
+MIT License
+
+Copyright (c) {holders}
+
+Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the \"Software\"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+";
+
+const APACHE_2_LICENSE: &str = include_str!("apache-2.0.txt");
+
 impl Rule for HasLicenseFile {
+    fn id(&self) -> &str {
+        "has-license-file"
+    }
+
     fn description(&self) -> &'static str {
         "Should have a LICENSE file in the project directory."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "A LICENSE file informs potential users and developers how they may \
+             interact with the code.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some("Add a non-empty LICENSE file at the workspace root.")
+    }
+
+    /// Only available when the package's `license` field consists of
+    /// licenses with known texts, currently `MIT` and `Apache-2.0`.
+    fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+        let package = project.package.as_ref()?;
+        let holders = if package.authors.is_empty() {
+            format!("The {} Developers", package.name)
+        } else {
+            package.authors.join(", ")
+        };
+        Some(Scaffold {
+            path: project.workspace_root.join("LICENSE"),
+            contents: license_text(package.license.as_ref()?, &holders)?,
+        })
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
-        search_manifest_and_workspace_dir_for_nonempty_file_name_match(
+        let outcome = search_manifest_and_workspace_dir_for_nonempty_file_name_match(
             &HAS_LICENSE_FILE,
             context.cargo_manifest_file_path,
             context.metadata,
-        )
+        );
+        if outcome != RuleOutcome::Success {
+            context.findings.push(file_search_finding(
+                &HAS_LICENSE_FILE,
+                &manifest_and_workspace_dirs(context.cargo_manifest_file_path, context.metadata),
+            ));
+        }
+        outcome
     }
 }
+/// The starter LICENSE content for a cargo `license` expression, if every
+/// license it mentions has a known text.
+fn license_text(license_expression: &str, holders: &str) -> Option<String> {
+    let texts = license_expression
+        .split(&['/', '(', ')'][..])
+        .flat_map(|part| part.split(" OR "))
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| match name {
+            "MIT" => Some(MIT_LICENSE.replace("{holders}", holders)),
+            "Apache-2.0" => Some(APACHE_2_LICENSE.to_owned()),
+            _ => None,
+        })
+        .collect::<Option<Vec<String>>>()?;
+    match texts.len() {
+        0 => None,
+        1 => texts.into_iter().next(),
+        _ => Some(format!(
+            "This project is licensed under your choice of any of the following.\n\n{}",
+            texts.join("\n---\n\n")
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_support::*;
@@ -132,5 +223,20 @@ mod tests {
         } = execute_rule_against_project_dir_all_verbosities(dir.path(), &rule);
         assert_eq!(RuleOutcome::Failure, verbose.outcome);
         assert_eq!(RuleOutcome::Failure, not_verbose.outcome);
+        assert_eq!(1, not_verbose.findings.len());
+        assert!(not_verbose.findings[0].message.contains("LICENSE"));
+    }
+
+    #[test]
+    fn license_text_covers_known_licenses_only() {
+        let mit = license_text("MIT", "Jo").expect("Should know MIT");
+        assert!(mit.starts_with("MIT License\n\nCopyright (c) Jo\n"));
+        let dual = license_text("MIT OR Apache-2.0", "Jo").expect("Should know both");
+        assert!(dual.contains("MIT License"));
+        assert!(dual.contains("Apache License, Version 2.0"));
+        assert!(dual.contains("TERMS AND CONDITIONS FOR USE, REPRODUCTION, AND DISTRIBUTION"));
+        assert_eq!(Some(dual), license_text("MIT/Apache-2.0", "Jo"));
+        assert_eq!(None, license_text("MIT OR GPL-3.0", "Jo"));
+        assert_eq!(None, license_text("", "Jo"));
     }
 }
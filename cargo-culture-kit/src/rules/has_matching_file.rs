@@ -0,0 +1,246 @@
+use super::super::file::{file_search_finding, find_child_file, find_descendant_file};
+use super::{Finding, Rule, RuleContext, RuleOutcome};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Where a `HasMatchingFile` rule looks for its file.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileSearchScope {
+    /// The directory containing the `Cargo.toml` under evaluation
+    PackageDir,
+    /// The root directory of the workspace, per the cargo metadata
+    WorkspaceRoot,
+    /// The `.github` directories of the package and of the workspace root
+    Github,
+    /// The package directory and all of its subdirectories, other than
+    /// `target` and `.git`
+    Recursive,
+}
+
+/// Rule that asserts a good Rust project has a file whose name matches
+/// a regular expression, such as a `CODEOWNERS` or `deny.toml` file.
+///
+/// Unlike the other `Rule`s in this crate, the description, file name
+/// pattern and search scope are supplied at runtime, typically from the
+/// `file-rules` of a `CultureConfig`.
+///
+/// By default, the package directory and workspace root are searched for
+/// a non-empty file, just like `HasLicenseFile` does.
+#[derive(Debug)]
+pub struct HasMatchingFile {
+    id: String,
+    description: String,
+    file_name_regex: Regex,
+    scopes: Vec<FileSearchScope>,
+    require_non_empty: bool,
+    justification: Option<String>,
+    remediation: Option<String>,
+}
+
+impl HasMatchingFile {
+    /// Create a `HasMatchingFile` that searches the package directory and
+    /// workspace root for a non-empty file with a name matching
+    /// `file_name_regex`.
+    pub fn new<I: Into<String>, D: Into<String>>(
+        id: I,
+        description: D,
+        file_name_regex: Regex,
+    ) -> Self {
+        HasMatchingFile {
+            id: id.into(),
+            description: description.into(),
+            file_name_regex,
+            scopes: vec![FileSearchScope::PackageDir, FileSearchScope::WorkspaceRoot],
+            require_non_empty: true,
+            justification: None,
+            remediation: None,
+        }
+    }
+
+    /// Search only the supplied `scopes`.
+    pub fn with_scopes(mut self, scopes: Vec<FileSearchScope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Whether a matching file must have some content to satisfy the `Rule`.
+    pub fn with_require_non_empty(mut self, require_non_empty: bool) -> Self {
+        self.require_non_empty = require_non_empty;
+        self
+    }
+
+    /// Supply the `Rule::justification`.
+    pub fn with_justification<S: Into<String>>(mut self, justification: S) -> Self {
+        self.justification = Some(justification.into());
+        self
+    }
+
+    /// Supply the `Rule::remediation`.
+    pub fn with_remediation<S: Into<String>>(mut self, remediation: S) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    /// The directories to search, each paired with whether its
+    /// subdirectories should be searched as well
+    fn search_dirs(&self, context: &RuleContext) -> Vec<(PathBuf, bool)> {
+        let package_dir = context
+            .cargo_manifest_file_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let workspace_root = context
+            .metadata
+            .as_ref()
+            .filter(|m| !m.workspace_root.is_empty())
+            .map(|m| PathBuf::from(&m.workspace_root));
+        let mut dirs: Vec<(PathBuf, bool)> = Vec::new();
+        for scope in &self.scopes {
+            let scope_dirs = match *scope {
+                FileSearchScope::PackageDir => vec![(package_dir.clone(), false)],
+                FileSearchScope::WorkspaceRoot => {
+                    workspace_root.iter().map(|w| (w.clone(), false)).collect()
+                }
+                FileSearchScope::Github => Some(&package_dir)
+                    .into_iter()
+                    .chain(workspace_root.as_ref())
+                    .map(|d| (d.join(".github"), false))
+                    .collect(),
+                FileSearchScope::Recursive => vec![(package_dir.clone(), true)],
+            };
+            for dir in scope_dirs {
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+        dirs
+    }
+
+    fn search_finding(&self, dirs: &[(PathBuf, bool)]) -> Finding {
+        let dir_paths: Vec<PathBuf> = dirs.iter().map(|dir| dir.0.clone()).collect();
+        if self.require_non_empty && dirs.iter().all(|&(_, recursive)| !recursive) {
+            return file_search_finding(&self.file_name_regex, &dir_paths);
+        }
+        let dir_names: Vec<String> = dirs
+            .iter()
+            .map(|&(ref d, recursive)| {
+                if recursive {
+                    format!("{} and its subdirectories", d.display())
+                } else {
+                    d.display().to_string()
+                }
+            })
+            .collect();
+        Finding::new(format!(
+            "Searched {} for a {}file with a name matching `{}`",
+            dir_names.join(", "),
+            if self.require_non_empty { "non-empty " } else { "" },
+            self.file_name_regex.as_str()
+        ))
+    }
+}
+
+impl Rule for HasMatchingFile {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn justification(&self) -> Option<&str> {
+        self.justification.as_deref()
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        self.remediation.as_deref()
+    }
+
+    fn evaluate(&self, context: RuleContext) -> RuleOutcome {
+        let dirs = self.search_dirs(&context);
+        let mut outcome = if dirs.is_empty() {
+            RuleOutcome::Undetermined
+        } else {
+            RuleOutcome::Failure
+        };
+        for &(ref dir, recursive) in &dirs {
+            // Directories that don't exist, such as an absent `.github`,
+            // simply don't contain the file
+            if !dir.is_dir() {
+                continue;
+            }
+            let dir_outcome = if recursive {
+                find_descendant_file(&self.file_name_regex, dir, self.require_non_empty)
+            } else {
+                find_child_file(&self.file_name_regex, dir, self.require_non_empty)
+            };
+            match dir_outcome {
+                RuleOutcome::Success => return RuleOutcome::Success,
+                RuleOutcome::Undetermined => outcome = RuleOutcome::Undetermined,
+                RuleOutcome::Failure | RuleOutcome::Skipped => (),
+            }
+        }
+        context.findings.push(self.search_finding(&dirs));
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::*;
+    use super::*;
+    use std::fs::{create_dir_all, File};
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn codeowners_rule() -> HasMatchingFile {
+        HasMatchingFile::new(
+            "has-codeowners-file",
+            "Should have a CODEOWNERS file.",
+            Regex::new("^CODEOWNERS$").expect("Could not make regex"),
+        )
+    }
+
+    #[test]
+    fn has_matching_file_only_searches_requested_scopes() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let github_dir = dir.path().join(".github");
+        create_dir_all(&github_dir).expect("Could not make .github dir");
+        File::create(github_dir.join("CODEOWNERS"))
+            .and_then(|mut f| f.write_all(b"* @maintainers"))
+            .expect("Could not write file");
+
+        let outcomes = execute_rule_against_project_dir_all_verbosities(
+            dir.path(),
+            &codeowners_rule(),
+        );
+        assert_eq!(RuleOutcome::Failure, outcomes.verbose.outcome);
+        assert_eq!(1, outcomes.not_verbose.findings.len());
+
+        let rule = codeowners_rule()
+            .with_scopes(vec![FileSearchScope::PackageDir, FileSearchScope::Github]);
+        let outcomes = execute_rule_against_project_dir_all_verbosities(dir.path(), &rule);
+        assert_eq!(RuleOutcome::Success, outcomes.verbose.outcome);
+        assert_eq!(RuleOutcome::Success, outcomes.not_verbose.outcome);
+    }
+
+    #[test]
+    fn has_matching_file_recursive_may_allow_empty_files() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let nested = dir.path().join("ci").join("config");
+        create_dir_all(&nested).expect("Could not make nested dirs");
+        File::create(nested.join("CODEOWNERS")).expect("Could not create file");
+
+        let rule = codeowners_rule().with_scopes(vec![FileSearchScope::Recursive]);
+        let capture = execute_rule_against_project_dir(dir.path(), &rule, false);
+        assert_eq!(RuleOutcome::Failure, capture.outcome);
+        assert!(capture.findings[0].message.contains("non-empty"));
+
+        let rule = rule.with_require_non_empty(false);
+        let capture = execute_rule_against_project_dir(dir.path(), &rule, false);
+        assert_eq!(RuleOutcome::Success, capture.outcome);
+    }
+}
@@ -1,6 +1,10 @@
-use super::super::file::shallow_scan_project_dir_for_nonempty_file_name_match;
+use super::super::file::{
+    file_search_finding, shallow_scan_project_dir_for_nonempty_file_name_match,
+};
+use super::super::scaffold::{ProjectInfo, Scaffold};
 use super::{Rule, RuleContext, RuleOutcome};
 use regex::Regex;
+use std::path::PathBuf;
 
 /// Rule that asserts a good Rust project:
 /// "Should have a README.md file in the project directory."
@@ -17,16 +21,72 @@ lazy_static! {
         Regex::new(r"^README\.?.*").expect("Failed to create HasReadmeFile regex.");
 }
 
+const README_MD: &str = "\
+# {name}
+
+{summary}
+
+## Building
+
+    cargo build
+
+## Testing
+
+    cargo test
+";
+
 impl Rule for HasReadmeFile {
+    fn id(&self) -> &str {
+        "has-readme-file"
+    }
+
     fn description(&self) -> &'static str {
         "Should have a README.md file in the project directory."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "A README file is likely the first and last piece of documentation people \
+             may read about a project.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some("Add a non-empty README file next to the project's Cargo.toml.")
+    }
+
+    fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+        let name = project.name();
+        let summary = project
+            .package
+            .as_ref()
+            .and_then(|p| p.description.clone())
+            .unwrap_or_else(|| format!("TODO: describe what {} does.", name));
+        Some(Scaffold {
+            path: project.manifest_dir.join("README.md"),
+            contents: README_MD
+                .replace("{name}", &name)
+                .replace("{summary}", summary.trim()),
+        })
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
-        shallow_scan_project_dir_for_nonempty_file_name_match(
+        let outcome = shallow_scan_project_dir_for_nonempty_file_name_match(
             &HAS_README_FILE,
             context.cargo_manifest_file_path,
-        )
+        );
+        if outcome != RuleOutcome::Success {
+            let project_dirs: Vec<PathBuf> = context
+                .cargo_manifest_file_path
+                .parent()
+                .map(|d| d.to_path_buf())
+                .into_iter()
+                .collect();
+            context
+                .findings
+                .push(file_search_finding(&HAS_README_FILE, &project_dirs));
+        }
+        outcome
     }
 }
 
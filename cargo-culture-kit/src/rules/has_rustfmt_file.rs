@@ -1,4 +1,8 @@
-use super::super::file::search_manifest_and_workspace_dir_for_nonempty_file_name_match;
+use super::super::file::{
+    file_search_finding, manifest_and_workspace_dirs,
+    search_manifest_and_workspace_dir_for_nonempty_file_name_match,
+};
+use super::super::scaffold::{ProjectInfo, Scaffold};
 use super::{Rule, RuleContext, RuleOutcome};
 use regex::Regex;
 
@@ -25,17 +29,54 @@ lazy_static! {
         Regex::new(r"^\.?(legacy-)?rustfmt.toml$").expect("Failed to create HasRustfmtFile regex.");
 }
 
+const RUSTFMT_TOML: &str = "\
+# Formatting options for `cargo fmt`. Any option not set here uses its default.
+# See https://github.com/rust-lang-nursery/rustfmt/blob/master/Configurations.md
+";
+
 impl Rule for HasRustfmtFile {
+    fn id(&self) -> &str {
+        "has-rustfmt-file"
+    }
+
     fn description(&self) -> &'static str {
         "Should have a rustfmt.toml file in the project directory."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "A rustfmt.toml file shows that the maintainers have chosen a unified \
+             formatting style, which enhances project-wide consistency and readability.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some(
+            "Add a non-empty rustfmt.toml at the workspace root, even one that only \
+             contains comments in order to adopt the default style.",
+        )
+    }
+
+    fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+        Some(Scaffold {
+            path: project.workspace_root.join("rustfmt.toml"),
+            contents: String::from(RUSTFMT_TOML),
+        })
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
-        search_manifest_and_workspace_dir_for_nonempty_file_name_match(
+        let outcome = search_manifest_and_workspace_dir_for_nonempty_file_name_match(
             &HAS_RUSTFMT_FILE,
             context.cargo_manifest_file_path,
             context.metadata,
-        )
+        );
+        if outcome != RuleOutcome::Success {
+            context.findings.push(file_search_finding(
+                &HAS_RUSTFMT_FILE,
+                &manifest_and_workspace_dirs(context.cargo_manifest_file_path, context.metadata),
+            ));
+        }
+        outcome
     }
 }
 #[cfg(test)]
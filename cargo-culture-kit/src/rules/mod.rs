@@ -2,9 +2,11 @@
 //! available through the `default_rules()` function.
 mod builds_cleanly_without_warnings_or_errors;
 mod cargo_metadata_readable;
+mod dependency_policy;
 mod has_continuous_integration_file;
 mod has_contributing_file;
 mod has_license_file;
+mod has_matching_file;
 mod has_readme_file;
 mod has_rustfmt_file;
 mod passes_multiple_tests;
@@ -13,19 +15,23 @@ mod uses_property_based_test_library;
 
 pub use self::builds_cleanly_without_warnings_or_errors::BuildsCleanlyWithoutWarningsOrErrors;
 pub use self::cargo_metadata_readable::CargoMetadataReadable;
+pub use self::dependency_policy::{DependencyPolicy, DependencyRequirement};
 pub use self::has_continuous_integration_file::HasContinuousIntegrationFile;
 pub use self::has_contributing_file::HasContributingFile;
 pub use self::has_license_file::HasLicenseFile;
+pub use self::has_matching_file::{FileSearchScope, HasMatchingFile};
 pub use self::has_readme_file::HasReadmeFile;
 pub use self::has_rustfmt_file::HasRustfmtFile;
 pub use self::passes_multiple_tests::PassesMultipleTests;
 pub use self::under_source_control::UnderSourceControl;
 pub use self::uses_property_based_test_library::UsesPropertyBasedTestLibrary;
 
+use super::scaffold::{ProjectInfo, Scaffold};
 use cargo_metadata::Metadata;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 /// The result of a `Rule.evaluate` call.
 ///
@@ -35,7 +41,11 @@ use std::path::Path;
 /// `RuleOutcome::Failure` is not an anomalous situation from the operational
 /// standpoint of a `Rule` evaluation, and is distinct from a `RuleOutcome::
 /// Undetermined` value.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// `RuleOutcome::Skipped` is not returned by `Rule` implementations, but
+/// recorded in place of an evaluation whose `prerequisites` were not upheld.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RuleOutcome {
     /// The Rule's `description` is definitely true for this project
     Success,
@@ -45,17 +55,212 @@ pub enum RuleOutcome {
     /// upheld or not for this project. Let's admit that we don't know for
     /// sure one way or the other.
     Undetermined,
+    /// The Rule was not evaluated, because one of its `prerequisites` was
+    /// not upheld for this project
+    Skipped,
+}
+
+/// A rough indication of how much work the evaluation of a `Rule` takes,
+/// so that users can anticipate how long a check will run.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleCost {
+    /// Inspects files or metadata that are already at hand, and completes
+    /// practically instantly
+    Cheap,
+    /// Runs external processes such as a complete `cargo build`, and may
+    /// take minutes for a large project
+    Expensive,
+}
+
+impl Display for RuleCost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RuleCost::Cheap => write!(f, "cheap"),
+            RuleCost::Expensive => write!(f, "expensive"),
+        }
+    }
+}
+
+/// How much a `Rule` that is not upheld matters to the overall result
+/// of a culture check.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// A `Rule` that is not upheld fails the check
+    #[default]
+    Error,
+    /// An advisory `Rule`, which is reported prominently when not upheld,
+    /// but does not fail the check
+    Warning,
+    /// An informational `Rule`, which never fails the check
+    Info,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// A structured piece of evidence collected by a `Rule` in the course of
+/// its evaluation, explaining why the `RuleOutcome` came out as it did.
+///
+/// Unlike `RuleContext::print_output`, findings are collected regardless of
+/// verbosity, so that `Reporter`s and library callers can explain an outcome
+/// without parsing prose.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Finding {
+    /// Human-oriented summary of what was observed
+    pub message: String,
+    /// The file the finding concerns, if any
+    pub file: Option<PathBuf>,
+    /// The 1-based line within `file` the finding concerns, if known
+    pub line: Option<u64>,
+}
+
+impl Finding {
+    /// Create a `Finding` that does not concern any particular file.
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Finding {
+            message: message.into(),
+            file: None,
+            line: None,
+        }
+    }
+
+    /// Associate this `Finding` with a particular file.
+    pub fn with_file<P: Into<PathBuf>>(mut self, file: P) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Associate this `Finding` with a particular 1-based line of its file.
+    pub fn with_line(mut self, line: u64) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}: {}", file.display(), line, self.message),
+            (Some(file), None) => write!(f, "{}: {}", file.display(), self.message),
+            (None, _) => write!(f, "{}", self.message),
+        }
+    }
 }
 
 /// The core trait of this crate. A `Rule` describes an idiom or best-practice
 /// for projects and provides a means of evaluating whether that rule of thumb
 /// is being upheld.
-pub trait Rule: Debug {
-    /// The central tenet of this `Rule`. Serves as a **unique identifier** for
-    /// Rule instances, as well as a human-readable summary of what this
-    /// `Rule` means for a given project.
+///
+/// `Rule`s must be `Send` and `Sync` so that independent `Rule`s may be
+/// evaluated concurrently, as by `check_culture_with_jobs`.
+pub trait Rule: Debug + Send + Sync {
+    /// The central tenet of this `Rule`. Serves as a human-readable summary
+    /// of what this `Rule` means for a given project, and must be unique
+    /// among Rule instances.
     fn description(&self) -> &str;
 
+    /// A short, stable, machine-friendly **unique identifier** for this
+    /// `Rule`, such as `has-license-file`.
+    ///
+    /// Unlike the `description`, which may be reworded over time, the `id`
+    /// should never change once published, so that culture checklists and
+    /// downstream tooling may safely refer to it.
+    ///
+    /// Defaults to the `description`, though implementations are strongly
+    /// encouraged to supply a kebab-case identifier.
+    fn id(&self) -> &str {
+        self.description()
+    }
+
+    /// Why a project ought to uphold this `Rule`, in a few human-oriented
+    /// sentences.
+    ///
+    /// Defaults to `None`, for `Rule`s which consider their `description`
+    /// to be justification enough.
+    fn justification(&self) -> Option<&str> {
+        None
+    }
+
+    /// What a project maintainer can do to bring a project that fails this
+    /// `Rule` into compliance, in a short human-oriented instruction.
+    ///
+    /// Defaults to `None`, for `Rule`s with no general-purpose advice.
+    fn remediation(&self) -> Option<&str> {
+        None
+    }
+
+    /// How much work evaluating this `Rule` takes.
+    ///
+    /// Defaults to `RuleCost::Cheap`.
+    fn cost(&self) -> RuleCost {
+        RuleCost::Cheap
+    }
+
+    /// How much this `Rule` not being upheld matters to the overall result
+    /// of a check.
+    ///
+    /// Defaults to `Severity::Error`.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// The `id`s of the `Rule`s that must be upheld for an evaluation of
+    /// this `Rule` to be meaningful, such as `cargo-metadata-readable`.
+    ///
+    /// A `Rule` is evaluated after any of its prerequisites that are checked
+    /// alongside it, and is reported as `RuleOutcome::Skipped` instead when
+    /// one of them is not upheld. Prerequisites that are not being checked
+    /// are ignored.
+    ///
+    /// Defaults to none.
+    fn prerequisites(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// The files and directories whose content alone decides the outcome of
+    /// `evaluate`, such as `Cargo.toml` or `src`, relative to the project's
+    /// workspace root and to the directory of each package within it.
+    ///
+    /// While the inputs are unchanged, a check may reuse an earlier outcome
+    /// of this `Rule` rather than evaluate it again. The target directory and
+    /// the workspace's `.git` directory are never considered part of the
+    /// inputs, and linked directories are not followed.
+    ///
+    /// Defaults to none, in which case the `Rule` is always evaluated.
+    fn inputs(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// The options this `Rule` was configured with, serialized in a stable
+    /// form, such as `{"minimum-passing-tests":4}`.
+    ///
+    /// Along with the `id`, this tells apart the cached outcomes of
+    /// differently configured `Rule`s, so a `Rule` that declares `inputs`
+    /// ought to supply it when it has options that affect its outcome.
+    ///
+    /// Defaults to empty, for `Rule`s without options.
+    fn configuration(&self) -> String {
+        String::new()
+    }
+
+    /// Starter content for a file that would bring a project which fails
+    /// this `Rule` into compliance, for use by `plan_scaffolds`.
+    ///
+    /// Defaults to `None`, for `Rule`s that cannot be satisfied by adding
+    /// a single file.
+    fn scaffold(&self, _project: &ProjectInfo) -> Option<Scaffold> {
+        None
+    }
+
     /// Does the Rust project found at `cargo_manifest_path` uphold this
     /// `Rule`, as summarized in the `description`?
     fn evaluate(&self, context: RuleContext) -> RuleOutcome;
@@ -75,11 +280,46 @@ pub struct RuleContext<'a> {
     /// Ought to be `None` only when the cargo metadata retrieval or parsing
     /// fails.
     pub metadata: &'a Option<Metadata>,
+    /// When the subprocesses this `Rule` starts, such as `cargo build`,
+    /// ought to be killed, if ever. `Rule` implementations that find their
+    /// subprocesses killed should report `RuleOutcome::Undetermined`.
+    pub deadline: Option<Instant>,
     /// Output `Write` implementation intended for supplying optional
     /// textual content visible to the end-user.  `Rule` implementations
     /// may make use of this as they wish, the default convention is to only
     /// write extra content when `verbose == true`
     pub print_output: &'a mut Write,
+    /// Collects structured `Finding`s that explain the `RuleOutcome`.
+    /// `Rule` implementations should supply findings regardless of
+    /// `verbose`, most commonly when the outcome is not a success.
+    pub findings: &'a mut Vec<Finding>,
+}
+
+impl<'a> RuleContext<'a> {
+    /// Create a `RuleContext` without a `deadline`. Unlike a struct literal,
+    /// code that uses `new` keeps compiling as fields are added.
+    pub fn new(
+        cargo_manifest_file_path: &'a Path,
+        verbose: bool,
+        metadata: &'a Option<Metadata>,
+        print_output: &'a mut Write,
+        findings: &'a mut Vec<Finding>,
+    ) -> Self {
+        RuleContext {
+            cargo_manifest_file_path,
+            verbose,
+            metadata,
+            deadline: None,
+            print_output,
+            findings,
+        }
+    }
+
+    /// Supply the `deadline` for the subprocesses the `Rule` starts.
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
 }
 
 /// Constructs new instances of the default `Rule`s
@@ -99,10 +339,61 @@ pub fn default_rules() -> Vec<Box<Rule>> {
     ]
 }
 
+/// The `Rule::inputs` of the `Rule`s that build the project, which are the
+/// files and directories that cargo reads by convention
+pub(crate) fn build_inputs() -> Vec<PathBuf> {
+    [
+        ".cargo",
+        "Cargo.toml",
+        "Cargo.lock",
+        "build.rs",
+        "src",
+        "tests",
+        "benches",
+        "examples",
+    ]
+        .iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// The name of the directory, within a project's target directory, in which
+/// the `Rule`s that build the project do so
+pub const CULTURE_TARGET_DIR_NAME: &str = "culture";
+
+/// The `CARGO_TARGET_DIR` shared by the `Rule`s that build the project, such
+/// as `BuildsCleanlyWithoutWarningsOrErrors` and `PassesMultipleTests`.
+///
+/// It is reused from one check to the next, but kept apart from the
+/// developer's own builds, so that cleaning it does not throw away their
+/// incremental build artifacts.
+pub(crate) fn culture_target_dir(
+    cargo_manifest_file_path: &Path,
+    metadata: &Option<Metadata>,
+) -> PathBuf {
+    let target_dir = match *metadata {
+        Some(ref m) if !m.target_directory.is_empty() => PathBuf::from(&m.target_directory),
+        _ => cargo_manifest_file_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join("target"),
+    };
+    target_dir.join(CULTURE_TARGET_DIR_NAME)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashSet;
+
+    #[test]
+    fn culture_target_dir_without_metadata_is_beside_the_manifest() {
+        assert_eq!(
+            PathBuf::from("project/target/culture"),
+            culture_target_dir(Path::new("project/Cargo.toml"), &None)
+        );
+    }
+
     #[test]
     fn default_rules_all_have_unique_descriptions() {
         let rules = default_rules();
@@ -112,11 +403,28 @@ mod tests {
         }
         assert_eq!(rules.len(), set.len());
     }
+
+    #[test]
+    fn default_rules_all_have_unique_kebab_case_ids() {
+        let rules = default_rules();
+        let mut set = HashSet::new();
+        for r in &rules {
+            assert!(
+                r.id()
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c == '-'),
+                "{} is not kebab-case",
+                r.id()
+            );
+            set.insert(r.id().to_string());
+        }
+        assert_eq!(rules.len(), set.len());
+    }
 }
 
 #[cfg(test)]
 pub(crate) mod test_support {
-    use super::{Rule, RuleContext, RuleOutcome};
+    use super::{Finding, Rule, RuleContext, RuleOutcome};
     use cargo_metadata;
     use std::fs::{create_dir_all, File};
     use std::io::Write;
@@ -130,6 +438,7 @@ pub(crate) mod test_support {
     pub struct OutcomeCapture {
         pub outcome: RuleOutcome,
         pub print_output: Vec<u8>,
+        pub findings: Vec<Finding>,
     }
 
     pub fn execute_rule_against_project_dir_all_verbosities(
@@ -150,15 +459,18 @@ pub(crate) mod test_support {
         let cargo_manifest_file_path = project_dir.join("Cargo.toml");
         let metadata = cargo_metadata::metadata(Some(cargo_manifest_file_path.as_ref())).ok();
         let mut print_output: Vec<u8> = Vec::new();
-        let outcome = rule.evaluate(RuleContext {
-            cargo_manifest_file_path: &cargo_manifest_file_path,
+        let mut findings = Vec::new();
+        let outcome = rule.evaluate(RuleContext::new(
+            &cargo_manifest_file_path,
             verbose,
-            metadata: &metadata,
-            print_output: &mut print_output,
-        });
+            &metadata,
+            &mut print_output,
+            &mut findings,
+        ));
         OutcomeCapture {
             outcome,
             print_output,
+            findings,
         }
     }
 
@@ -1,5 +1,7 @@
-use super::{Rule, RuleContext, RuleOutcome};
+use super::super::process::{self, RunError};
+use super::{build_inputs, culture_target_dir, Finding, Rule, RuleContext, RuleCost, RuleOutcome};
 use regex::Regex;
+use std::path::PathBuf;
 use std::process::Command;
 use std::str::from_utf8;
 
@@ -14,14 +16,38 @@ use std::str::from_utf8;
 /// one, as even brand-new `cargo` library projects are supplied with
 /// a dummy test by default.
 ///
+/// The minimum number of passing tests defaults to two, and can be raised
+/// with `PassesMultipleTests::new`.
+///
 /// # Caveats
 ///
 /// This rule will actually attempt to run a project's tests through
 /// `cargo test`. If this `Rule` is executed before the project has
 /// been built or tested at all, the process of acquiring dependencies
-/// and building them may take a while.
-#[derive(Default, Debug)]
-pub struct PassesMultipleTests;
+/// and building them may take a while. The tests are built in a `culture`
+/// directory within the project's target directory, shared with
+/// `BuildsCleanlyWithoutWarningsOrErrors`, so the project is not compiled
+/// twice.
+#[derive(Debug)]
+pub struct PassesMultipleTests {
+    minimum_passing_tests: usize,
+}
+
+impl PassesMultipleTests {
+    /// Create a `PassesMultipleTests` that requires at least
+    /// `minimum_passing_tests` tests to pass.
+    pub fn new(minimum_passing_tests: usize) -> Self {
+        PassesMultipleTests {
+            minimum_passing_tests,
+        }
+    }
+}
+
+impl Default for PassesMultipleTests {
+    fn default() -> Self {
+        PassesMultipleTests::new(2)
+    }
+}
 
 const CARGO_CULTURE_TEST_RECURSION_BUSTER: &str = "CARGO_CULTURE_TEST_RECURSION_BUSTER";
 
@@ -32,15 +58,46 @@ lazy_static! {
 }
 
 impl Rule for PassesMultipleTests {
+    fn id(&self) -> &str {
+        "passes-multiple-tests"
+    }
+
     fn description(&self) -> &'static str {
         "Should have multiple tests which pass."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "Some degree of automated testing is necessary for nearly all code, and \
+             even brand-new cargo library projects come with a dummy test.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some("Add at least two tests, and make sure `cargo test` passes.")
+    }
+
+    fn cost(&self) -> RuleCost {
+        RuleCost::Expensive
+    }
+
+    fn prerequisites(&self) -> Vec<&str> {
+        vec!["cargo-metadata-readable"]
+    }
+
+    fn inputs(&self) -> Vec<PathBuf> {
+        build_inputs()
+    }
+
+    fn configuration(&self) -> String {
+        json!({ "minimum-passing-tests": self.minimum_passing_tests }).to_string()
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
         match ::std::env::var(CARGO_CULTURE_TEST_RECURSION_BUSTER) {
             Ok(_) => RuleOutcome::Success, // Don't recurse indefinitely
             Err(_) => {
-                let mut test_cmd = Command::new(&get_cargo_command());
+                let mut test_cmd = Command::new(get_cargo_command());
                 test_cmd
                     .arg("test")
                     .arg("--manifest-path")
@@ -50,12 +107,22 @@ impl Rule for PassesMultipleTests {
                     .arg("--verbose")
                     .arg("--")
                     .arg("--nocapture")
+                    .env(
+                        "CARGO_TARGET_DIR",
+                        culture_target_dir(context.cargo_manifest_file_path, context.metadata),
+                    )
                     .env(CARGO_CULTURE_TEST_RECURSION_BUSTER, "true");
-                let test_output = match test_cmd.output() {
+                let test_output = match process::run(&mut test_cmd, None, context.deadline) {
                     Ok(o) => o,
-                    Err(_) => {
+                    Err(RunError::Launch(_)) => {
                         return RuleOutcome::Failure;
                     }
+                    Err(e) => {
+                        context
+                            .findings
+                            .push(Finding::new(format!("`cargo test` {}", e)));
+                        return RuleOutcome::Undetermined;
+                    }
                 };
 
                 if let Ok(s) = from_utf8(&test_output.stdout) {
@@ -68,9 +135,14 @@ impl Rule for PassesMultipleTests {
                             total_passed += num_passed;
                         }
                     }
-                    if total_passed > 1 {
+                    if total_passed >= self.minimum_passing_tests {
                         RuleOutcome::Success
                     } else {
+                        context.findings.push(Finding::new(format!(
+                            "`cargo test` reported {} passing test(s), \
+                             but at least {} are required",
+                            total_passed, self.minimum_passing_tests
+                        )));
                         RuleOutcome::Failure
                     }
                 } else {
@@ -169,6 +241,23 @@ mod tests {
         assert_eq!(RuleOutcome::Failure, not_verbose.outcome);
     }
 
+    #[test]
+    fn passes_multiple_tests_fails_below_configured_minimum() {
+        if var(CARGO_CULTURE_TEST_RECURSION_BUSTER).is_ok() {
+            return;
+        }
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), None);
+        write_lib_file_with_dummy_tests(dir.path(), 3);
+        let rule = PassesMultipleTests::new(4);
+        let VerbosityOutcomes {
+            verbose,
+            not_verbose,
+        } = execute_rule_against_project_dir_all_verbosities(dir.path(), &rule);
+        assert_eq!(RuleOutcome::Failure, verbose.outcome);
+        assert_eq!(RuleOutcome::Failure, not_verbose.outcome);
+    }
+
     fn write_lib_file_with_dummy_tests(project_dir: &Path, num_tests: usize) {
         let src_dir = project_dir.join("src");
         create_dir_all(&src_dir).expect("Could not create src dir");
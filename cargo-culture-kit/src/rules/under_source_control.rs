@@ -1,4 +1,4 @@
-use super::{Rule, RuleContext, RuleOutcome};
+use super::{Finding, Rule, RuleContext, RuleOutcome};
 use std::path::Path;
 
 /// Rule that asserts a good Rust project:
@@ -27,16 +27,40 @@ pub struct UnderSourceControl;
 const VC_SUBDIRS: &[&str] = &[".git", ".hg", ".bzr", ".svn", "_darcs"];
 
 impl Rule for UnderSourceControl {
+    fn id(&self) -> &str {
+        "under-source-control"
+    }
+
     fn description(&self) -> &str {
         "Should be under source control."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "Source control is essential for the coordinated development of software \
+             projects.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some("Put the project under source control, e.g. with `git init`.")
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
         if AncestorDirs::from_file(context.cargo_manifest_file_path)
             .any(|dir| VC_SUBDIRS.iter().any(|subdir| dir.join(subdir).is_dir()))
         {
             RuleOutcome::Success
         } else {
+            context.findings.push(Finding::new(format!(
+                "None of {} was found in {} or any of its ancestors",
+                VC_SUBDIRS.join(", "),
+                context
+                    .cargo_manifest_file_path
+                    .parent()
+                    .unwrap_or(context.cargo_manifest_file_path)
+                    .display()
+            )));
             RuleOutcome::Failure
         }
     }
@@ -84,6 +108,8 @@ mod tests {
         } = execute_rule_against_project_dir_all_verbosities(dir.path(), &rule);
         assert_eq!(RuleOutcome::Failure, verbose.outcome);
         assert_eq!(RuleOutcome::Failure, not_verbose.outcome);
+        assert_eq!(verbose.findings, not_verbose.findings);
+        assert!(!not_verbose.findings.is_empty());
     }
 
     #[test]
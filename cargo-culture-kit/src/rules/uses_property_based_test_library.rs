@@ -1,4 +1,4 @@
-use super::{Rule, RuleContext, RuleOutcome};
+use super::{Finding, Rule, RuleContext, RuleOutcome};
 use cargo_metadata::DependencyKind;
 use regex::Regex;
 
@@ -44,10 +44,33 @@ lazy_static! {
 }
 
 impl Rule for UsesPropertyBasedTestLibrary {
+    fn id(&self) -> &str {
+        "uses-property-based-test-library"
+    }
+
     fn description(&self) -> &'static str {
         "Should be making an effort to use property based tests."
     }
 
+    fn justification(&self) -> Option<&str> {
+        Some(
+            "Property based tests generate many inputs for each test, encouraging \
+             reasoning about the state-space of the program and rapidly achieving more \
+             comprehensive correctness assurances.",
+        )
+    }
+
+    fn remediation(&self) -> Option<&str> {
+        Some(
+            "Add `proptest` or `quickcheck` as a dev-dependency of each package and \
+             write some property based tests.",
+        )
+    }
+
+    fn prerequisites(&self) -> Vec<&str> {
+        vec!["cargo-metadata-readable"]
+    }
+
     fn evaluate(&self, context: RuleContext) -> RuleOutcome {
         match *context.metadata {
             None => RuleOutcome::Undetermined,
@@ -55,6 +78,7 @@ impl Rule for UsesPropertyBasedTestLibrary {
                 if m.packages.is_empty() {
                     return RuleOutcome::Undetermined;
                 }
+                let mut outcome = RuleOutcome::Success;
                 for package in &m.packages {
                     let has_pbt_dep = package
                         .dependencies
@@ -62,10 +86,17 @@ impl Rule for UsesPropertyBasedTestLibrary {
                         .filter(|d| d.kind == DependencyKind::Development)
                         .any(|d| USES_PROPERTY_BASED_TEST_LIBRARY.is_match(&d.name));
                     if !has_pbt_dep {
-                        return RuleOutcome::Failure;
+                        context.findings.push(
+                            Finding::new(format!(
+                                "Package `{}` lacks a proptest, quickcheck or suppositions \
+                                 dev-dependency",
+                                package.name
+                            )).with_file(&package.manifest_path),
+                        );
+                        outcome = RuleOutcome::Failure;
                     }
                 }
-                RuleOutcome::Success
+                outcome
             }
         }
     }
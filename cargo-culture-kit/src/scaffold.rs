@@ -0,0 +1,374 @@
+//! Generation of starter content for projects that fail file-based `Rule`s.
+//!
+//! A `Rule` may offer a `Scaffold` through `Rule::scaffold`. `plan_scaffolds`
+//! evaluates the `Rule`s that do so and collects the `Scaffold`s of those
+//! that failed, which can then be listed or written with `Scaffold::write`.
+use super::{check_culture, CheckError, Reporter, Rule, RuleOutcome};
+use serde_json::{self, Value};
+use std::fs::{create_dir_all, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Errors specific to writing `Scaffold`s to disk.
+#[derive(Debug, Clone, Eq, Fail, PartialEq, Hash)]
+pub enum ScaffoldError {
+    #[fail(display = "Refusing to overwrite the existing file {}", _0)]
+    /// The `Scaffold`'s destination already exists. Existing files are never
+    /// overwritten.
+    FileAlreadyExists(String),
+    #[fail(display = "There was an error while attempting to write {}: {}", path, cause)]
+    /// Failure creating the destination file or its parent directories.
+    WriteFailure {
+        /// The destination that could not be written
+        path: String,
+        /// A human-oriented description of the underlying problem
+        cause: String,
+    },
+    /// Destructuring should not be exhaustive.
+    ///
+    /// This enum may grow additional variants, so this hidden variant
+    /// ensures users do not rely on exhaustive matching.
+    #[doc(hidden)]
+    #[fail(display = "A hidden variant to increase expansion flexibility")]
+    __Nonexhaustive,
+}
+
+/// A file, and its starter content, that would bring a project into
+/// compliance with some `Rule`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scaffold {
+    /// Where the file should be created
+    pub path: PathBuf,
+    /// The complete starter content of the file
+    pub contents: String,
+}
+
+impl Scaffold {
+    /// Create the file at `path`, along with any missing parent directories,
+    /// and fill it with `contents`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScaffoldError::FileAlreadyExists` rather than overwriting
+    /// a file that is already present.
+    pub fn write(&self) -> Result<(), ScaffoldError> {
+        let write_failure = |e: ::std::io::Error| ScaffoldError::WriteFailure {
+            path: self.path.display().to_string(),
+            cause: e.to_string(),
+        };
+        if let Some(parent) = self.path.parent() {
+            create_dir_all(parent).map_err(&write_failure)?;
+        }
+        let mut file = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.path)
+        {
+            Ok(f) => f,
+            Err(ref e) if e.kind() == ErrorKind::AlreadyExists => {
+                return Err(ScaffoldError::FileAlreadyExists(
+                    self.path.display().to_string(),
+                ))
+            }
+            Err(e) => return Err(write_failure(e)),
+        };
+        file.write_all(self.contents.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(write_failure)
+    }
+}
+
+/// The project-level facts available to `Rule`s when producing a `Scaffold`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectInfo {
+    /// The directory containing the project's `Cargo.toml`
+    pub manifest_dir: PathBuf,
+    /// The root directory of the project's workspace, which is the same as
+    /// `manifest_dir` for single-package projects
+    pub workspace_root: PathBuf,
+    /// The package described by the project's `Cargo.toml`, if it describes
+    /// a package rather than only a workspace
+    pub package: Option<PackageInfo>,
+}
+
+/// The subset of a package's manifest that is useful for generating
+/// starter content.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackageInfo {
+    /// The package `name`
+    pub name: String,
+    /// The package `description`, if any
+    pub description: Option<String>,
+    /// The package `license` SPDX expression, if any
+    pub license: Option<String>,
+    /// The package `authors`
+    pub authors: Vec<String>,
+}
+
+impl ProjectInfo {
+    /// Gather `ProjectInfo` for the project whose manifest is found at
+    /// `cargo_manifest_file_path`.
+    ///
+    /// When `cargo metadata` is unavailable or fails, both directories
+    /// fall back to the directory containing the manifest and no
+    /// `package` is provided.
+    pub fn read(cargo_manifest_file_path: &Path) -> Self {
+        let manifest_dir = cargo_manifest_file_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_default();
+        let metadata = Command::new(get_cargo_command())
+            .arg("metadata")
+            .arg("--no-deps")
+            .arg("--format-version")
+            .arg("1")
+            .arg("--manifest-path")
+            .arg(cargo_manifest_file_path)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| serde_json::from_slice::<Value>(&o.stdout).ok());
+        let metadata = match metadata {
+            Some(m) => m,
+            None => {
+                return ProjectInfo {
+                    workspace_root: manifest_dir.clone(),
+                    manifest_dir,
+                    package: None,
+                }
+            }
+        };
+        let workspace_root = metadata["workspace_root"]
+            .as_str()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| manifest_dir.clone());
+        let canonical_manifest = cargo_manifest_file_path.canonicalize().ok();
+        let package = metadata["packages"]
+            .as_array()
+            .and_then(|packages| {
+                packages.iter().find(|p| {
+                    p["manifest_path"]
+                        .as_str()
+                        .and_then(|m| Path::new(m).canonicalize().ok())
+                        == canonical_manifest
+                })
+            })
+            .map(package_info);
+        ProjectInfo {
+            manifest_dir,
+            workspace_root,
+            package,
+        }
+    }
+
+    /// The name of the project, as suitable for headings and titles.
+    ///
+    /// Falls back to the name of the `manifest_dir` for workspaces.
+    pub fn name(&self) -> String {
+        match self.package {
+            Some(ref p) => p.name.clone(),
+            None => self
+                .manifest_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| String::from("project")),
+        }
+    }
+}
+
+fn package_info(package: &Value) -> PackageInfo {
+    let string = |key: &str| package[key].as_str().map(|s| s.to_owned());
+    PackageInfo {
+        name: string("name").unwrap_or_default(),
+        description: string("description"),
+        license: string("license"),
+        authors: package["authors"]
+            .as_array()
+            .map(|authors| {
+                authors
+                    .iter()
+                    .filter_map(|a| a.as_str().map(|s| s.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Evaluate each of the `rules` which offers a `Scaffold` for the project
+/// specified by `cargo_manifest_file_path`, and produce the `Scaffold`s
+/// of the ones that failed.
+///
+/// `Scaffold`s whose destination already exists are left out, as are any
+/// duplicate destinations, so every `Scaffold` returned can be written.
+///
+/// `verbose` and `reporter` are used for the evaluation exactly as in
+/// `check_culture`.
+///
+/// # Errors
+///
+/// Returns an error if the supplied `reporter` fails, most commonly
+/// because it cannot write to its output.
+pub fn plan_scaffolds<P: AsRef<Path>, R: Reporter + ?Sized>(
+    cargo_manifest_file_path: P,
+    verbose: bool,
+    reporter: &mut R,
+    rules: &[&Rule],
+) -> Result<Vec<Scaffold>, CheckError> {
+    let project = ProjectInfo::read(cargo_manifest_file_path.as_ref());
+    let (scaffoldable_rules, scaffolds): (Vec<&Rule>, Vec<(&str, Scaffold)>) = rules
+        .iter()
+        .filter_map(|r| r.scaffold(&project).map(|s| (*r, (r.id(), s))))
+        .unzip();
+    // The report follows the order in which the rules were evaluated,
+    // which need not be the order they were supplied in
+    let report = check_culture(
+        cargo_manifest_file_path,
+        verbose,
+        reporter,
+        &scaffoldable_rules,
+    )?;
+    let mut planned: Vec<Scaffold> = Vec::new();
+    for (id, scaffold) in scaffolds {
+        let failed = report
+            .iter()
+            .any(|r| r.id == id && r.outcome == RuleOutcome::Failure);
+        if failed
+            && !scaffold.path.exists()
+            && !planned.iter().any(|p| p.path == scaffold.path)
+        {
+            planned.push(scaffold);
+        }
+    }
+    Ok(planned)
+}
+
+fn get_cargo_command() -> String {
+    ::std::env::var("CARGO").unwrap_or_else(|_| String::from("cargo"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reporter::JsonReporter;
+    use rules::test_support::write_package_cargo_toml;
+    use std::fs::{read_to_string, File};
+    use tempfile::tempdir;
+    use rules::UnderSourceControl;
+    use {HasLicenseFile, HasReadmeFile, HasRustfmtFile};
+
+    #[test]
+    fn scaffold_write_refuses_to_overwrite() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let scaffold = Scaffold {
+            path: dir.path().join("nested").join("rustfmt.toml"),
+            contents: String::from("# Hello\n"),
+        };
+        scaffold.write().expect("Should write a fresh file");
+        assert_eq!(
+            "# Hello\n",
+            read_to_string(&scaffold.path).expect("Should read back")
+        );
+        let replacement = Scaffold {
+            contents: String::from("# Goodbye\n"),
+            ..scaffold.clone()
+        };
+        match replacement.write() {
+            Err(ScaffoldError::FileAlreadyExists(_)) => (),
+            other => panic!("Unexpected result {:?}", other),
+        }
+        assert_eq!(
+            "# Hello\n",
+            read_to_string(&scaffold.path).expect("Should read back")
+        );
+    }
+
+    #[test]
+    fn project_info_falls_back_to_manifest_dir() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let info = ProjectInfo::read(&dir.path().join("Cargo.toml"));
+        assert_eq!(dir.path(), info.manifest_dir.as_path());
+        assert_eq!(dir.path(), info.workspace_root.as_path());
+        assert_eq!(None, info.package);
+    }
+
+    #[test]
+    fn plan_scaffolds_only_covers_failing_rules_without_existing_files() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path(), None);
+        File::create(dir.path().join("README.md"))
+            .and_then(|mut f| f.write_all(b"# kid\n"))
+            .expect("Could not write README");
+        let license = HasLicenseFile::default();
+        let readme = HasReadmeFile::default();
+        let rustfmt = HasRustfmtFile::default();
+        let source_control = UnderSourceControl::default();
+        let mut reporter = JsonReporter::new(Vec::new());
+        let planned = plan_scaffolds(
+            dir.path().join("Cargo.toml"),
+            false,
+            &mut reporter,
+            &[&license, &readme, &rustfmt, &source_control],
+        ).expect("Should plan");
+        let paths: Vec<PathBuf> = planned.iter().map(|s| s.path.clone()).collect();
+        assert!(paths.contains(&dir.path().join("rustfmt.toml")));
+        assert!(!paths.contains(&dir.path().join("README.md")));
+        assert!(planned.iter().all(|s| !s.contents.is_empty()));
+    }
+
+    #[derive(Debug)]
+    struct ScaffoldingRule {
+        id: &'static str,
+        prerequisites: Vec<&'static str>,
+        outcome: RuleOutcome,
+    }
+
+    impl Rule for ScaffoldingRule {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn description(&self) -> &str {
+            self.id
+        }
+
+        fn prerequisites(&self) -> Vec<&str> {
+            self.prerequisites.clone()
+        }
+
+        fn scaffold(&self, project: &ProjectInfo) -> Option<Scaffold> {
+            Some(Scaffold {
+                path: project.workspace_root.join(self.id),
+                contents: String::from(self.id),
+            })
+        }
+
+        fn evaluate(&self, _context: ::RuleContext) -> RuleOutcome {
+            self.outcome.clone()
+        }
+    }
+
+    #[test]
+    fn plan_scaffolds_matches_rules_reordered_by_prerequisites() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        let dependent = ScaffoldingRule {
+            id: "dependent",
+            prerequisites: vec!["prerequisite"],
+            outcome: RuleOutcome::Failure,
+        };
+        let prerequisite = ScaffoldingRule {
+            id: "prerequisite",
+            prerequisites: Vec::new(),
+            outcome: RuleOutcome::Success,
+        };
+        let mut reporter = JsonReporter::new(Vec::new());
+        let planned = plan_scaffolds(
+            dir.path().join("Cargo.toml"),
+            false,
+            &mut reporter,
+            &[&dependent, &prerequisite],
+        ).expect("Should plan");
+        let paths: Vec<PathBuf> = planned.iter().map(|s| s.path.clone()).collect();
+        assert_eq!(vec![dir.path().join("dependent")], paths);
+    }
+}
@@ -0,0 +1,70 @@
+extern crate cargo_culture_kit;
+extern crate tempfile;
+
+use cargo_culture_kit::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::tempdir;
+
+/// Counts its evaluations, which should not happen once cancelled
+#[derive(Debug, Default)]
+struct CountingRule {
+    id: &'static str,
+    evaluations: AtomicUsize,
+}
+
+impl Rule for CountingRule {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn description(&self) -> &str {
+        self.id
+    }
+
+    fn evaluate(&self, _context: RuleContext) -> RuleOutcome {
+        self.evaluations.fetch_add(1, Ordering::SeqCst);
+        RuleOutcome::Success
+    }
+}
+
+// The cancellation is process-wide, so this is the only test in its binary
+#[test]
+fn rules_are_not_evaluated_once_cancelled() {
+    let dir = tempdir().expect("Failed to make a temp dir");
+    let manifest = dir.path().join("Cargo.toml");
+    let rules = [
+        CountingRule {
+            id: "first",
+            ..CountingRule::default()
+        },
+        CountingRule {
+            id: "second",
+            ..CountingRule::default()
+        },
+    ];
+    let rule_refs = rules.iter().map(|r| r as &Rule).collect::<Vec<&Rule>>();
+    cancel_subprocesses();
+    for jobs in [1, 2] {
+        let options = CheckOptions {
+            jobs,
+            ..CheckOptions::default()
+        };
+        let report = check_culture_with_options(
+            &manifest,
+            false,
+            &options,
+            &mut TextReporter::new(Vec::new()),
+            &rule_refs,
+        )
+        .expect("Should check");
+        assert_eq!(rules.len(), report.rules.len());
+        for rule_report in &report.rules {
+            assert_eq!(RuleOutcome::Undetermined, rule_report.outcome);
+            assert!(rule_report.findings[0].message.contains("cancelled"));
+        }
+    }
+    for rule in &rules {
+        assert_eq!(0, rule.evaluations.load(Ordering::SeqCst));
+    }
+}
@@ -37,7 +37,11 @@ fn assert_checks_default_culture(cargo_manifest_file_path: &Path) {
                 "About to dogfood self with a check_culture, using the manifest at: {:?}",
                 cargo_manifest_file_path
             );
-            let outcome = check_culture_default(cargo_manifest_file_path, true, &mut stderr())
+            let outcome = check_culture_default(
+                cargo_manifest_file_path,
+                true,
+                &mut TextReporter::new(stderr()),
+            )
                 .expect("Should have no errors running the checks");
 
             let def_rules = default_rules();
@@ -46,7 +50,7 @@ fn assert_checks_default_culture(cargo_manifest_file_path: &Path) {
             for r in def_rules {
                 assert_eq!(
                     Some(&RuleOutcome::Success),
-                    outcome.get(r.description()),
+                    outcome.get(r.description()).map(|r| &r.outcome),
                     "Rule of interest: {}",
                     r.description()
                 );
@@ -58,6 +62,9 @@ fn assert_checks_default_culture(cargo_manifest_file_path: &Path) {
                     success_count: 10,
                     fail_count: 0,
                     undetermined_count: 0,
+                    warning_count: 0,
+                    info_count: 0,
+                    skipped_count: 0,
                 },
                 stats
             );
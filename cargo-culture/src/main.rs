@@ -26,6 +26,7 @@
 //!
 //! ```
 extern crate cargo_culture_kit;
+extern crate ctrlc;
 extern crate failure;
 
 #[macro_use]
@@ -39,13 +40,19 @@ extern crate proptest;
 extern crate tempfile;
 
 use cargo_culture_kit::{
-    check_culture, check_culture_default, default_rules,
-    filter_to_requested_rules_from_checklist_file, find_extant_culture_file, ExitCode, FilterError,
-    OutcomesByDescription, Rule, DEFAULT_CULTURE_CHECKLIST_FILE_NAME,
+    cancel_subprocesses, check_culture_with_options, default_rules,
+    filter_to_requested_rules_from_checklist_file, find_extant_culture_config_file,
+    find_extant_culture_file, find_plugins_on_path, find_rule, format_checklist, plan_scaffolds,
+    subprocesses_cancelled, CheckOptions, CultureConfig, CultureReport, ExitCode, ExternalRule,
+    FilterError, HtmlReporter, JsonReporter, JunitReporter, MarkdownReporter, ProjectInfo,
+    Reporter, Rule, RuleOutcome, SarifReporter, Scaffold, ScaffoldError, TapReporter, TextReporter,
+    DEFAULT_CULTURE_CHECKLIST_FILE_NAME, DEFAULT_CULTURE_CONFIG_FILE_NAME, PLUGIN_DESCRIBE_TIMEOUT,
 };
 use failure::Error;
-use std::io::stdout;
+use std::io::{stdout, Write};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 use structopt::StructOpt;
 
 /// Parsing and representation of `cargo-culture` command line arguments.
@@ -56,17 +63,24 @@ pub enum Opt {
     #[structopt(name = "culture")]
     Culture {
         /// The location of the Cargo manifest for the project to check
-        #[structopt(long = "manifest-path", parse(from_os_str), default_value = "./Cargo.toml")]
+        #[structopt(
+            long = "manifest-path",
+            parse(from_os_str),
+            default_value = "./Cargo.toml"
+        )]
         manifest_path: PathBuf,
 
-        /// The file location of the line-separated list of Rule descriptions
-        /// to check for this project.
+        /// The file location of the line-separated list of Rule ids or
+        /// descriptions to check for this project, or of a TOML
+        /// configuration file of rules and their options when the file
+        /// name ends in `.toml`.
         ///
-        /// If absent, look for a culture checklist file named `".culture"` in
-        /// the current and ancestor directories of the project
+        /// If absent, look for a culture configuration file named
+        /// `".culture.toml"`, and then for a culture checklist file named
+        /// `".culture"`, in the current and ancestor directories of the project
         /// specified by `manifest_path`.
         ///
-        /// If no explicit file is specified and no implicit `".culture"` file
+        /// If no explicit file is specified and no implicit file
         /// is found, use the default rules.
         #[structopt(long = "culture-checklist-path", parse(from_os_str))]
         culture_checklist_file_path: Option<PathBuf>,
@@ -74,62 +88,451 @@ pub enum Opt {
         /// If present, emit extraneous explanations and superfluous details
         #[structopt(short = "v", long = "verbose")]
         verbose: bool,
+
+        /// The number of rules to evaluate concurrently. Expensive rules,
+        /// such as those that build the project, still run one at a time.
+        #[structopt(short = "j", long = "jobs", default_value = "1")]
+        jobs: usize,
+
+        /// The number of seconds after which any rule still running a
+        /// subprocess, such as `cargo test`, is stopped and reported as
+        /// undetermined
+        #[structopt(long = "timeout")]
+        timeout: Option<u64>,
+
+        /// The number of seconds each rule may spend running subprocesses,
+        /// such as `cargo test`, before being stopped and reported as
+        /// undetermined
+        #[structopt(long = "rule-timeout")]
+        rule_timeout: Option<u64>,
+
+        /// If present, evaluate every rule afresh, rather than reuse the
+        /// outcomes of rules whose inputs are unchanged since the last check
+        #[structopt(long = "no-cache")]
+        no_cache: bool,
+
+        /// The style of report to print, either human-oriented `text`, a `tap`
+        /// stream, a machine-readable `json`, `junit` XML, or `sarif` document,
+        /// or a shareable `markdown` or standalone `html` document
+        #[structopt(
+            long = "format",
+            default_value = "text",
+            raw(possible_values = "OutputFormat::NAMES")
+        )]
+        format: OutputFormat,
+
+        /// An action to take instead of checking the project
+        #[structopt(subcommand)]
+        command: Option<Command>,
+    },
+}
+
+/// Actions other than the default checking of a project's culture.
+#[derive(StructOpt, Debug, PartialEq)]
+pub enum Command {
+    /// Create starter content for the missing files of failing rules,
+    /// such as a LICENSE, README, or CI configuration.
+    ///
+    /// Existing files are never overwritten.
+    #[structopt(name = "fix")]
+    Fix {
+        /// List the files that would be created without writing them
+        #[structopt(long = "dry-run")]
+        dry_run: bool,
     },
+
+    /// Write a `.culture` checklist file listing the default rules
+    /// to the root of the project's workspace.
+    ///
+    /// An existing checklist file is never overwritten.
+    #[structopt(name = "init")]
+    Init {
+        /// Check the project first, and only select the rules it currently
+        /// passes. The remaining rules are listed as comments.
+        #[structopt(long = "only-passing")]
+        only_passing: bool,
+    },
+
+    /// Print every available rule with its id, cost and description
+    #[structopt(name = "list")]
+    List,
+
+    /// Print the description, rationale and remediation of a single rule
+    #[structopt(name = "explain")]
+    Explain {
+        /// The rule's id, its description, or any distinctive
+        /// fragment of its description
+        #[structopt(name = "RULE")]
+        rule: String,
+    },
+}
+
+/// The available styles of reporting the outcomes of a culture check.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// Human-oriented, colored prose
+    Text,
+    /// A single machine-readable JSON document
+    Json,
+    /// A JUnit XML document, with each rule represented as a test case
+    Junit,
+    /// A SARIF 2.1.0 log, for code-scanning integrations
+    Sarif,
+    /// A Test Anything Protocol stream
+    Tap,
+    /// A Markdown document, suitable for posting as a pull request comment
+    Markdown,
+    /// A standalone HTML page
+    Html,
+}
+
+impl OutputFormat {
+    /// The command line names of the available formats
+    pub const NAMES: &'static [&'static str] =
+        &["text", "json", "junit", "sarif", "tap", "markdown", "html"];
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "junit" => Ok(OutputFormat::Junit),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "tap" => Ok(OutputFormat::Tap),
+            "markdown" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            _ => Err(format!(
+                "Unknown output format \"{}\", expected one of: {}",
+                s,
+                OutputFormat::NAMES.join(", ")
+            )),
+        }
+    }
 }
 
 fn main() {
-    std::process::exit(
-        check_culture_cli(Opt::from_args())
-            .map_err(|e| {
-                println!("{}", e);
-                e
-            })
+    let cli_options = Opt::from_args();
+    // Child cargo processes run in process groups of their own, so they
+    // must be stopped on our behalf. A second interrupt gives up waiting.
+    let _ = ctrlc::set_handler(|| {
+        if subprocesses_cancelled() {
+            std::process::exit(130);
+        }
+        cancel_subprocesses();
+    });
+    let exit_code = match cli_options {
+        Opt::Culture {
+            command: Some(Command::Fix { .. }),
+            ..
+        } => print_error(fix_culture_cli(cli_options))
+            .map(|_| RuleOutcome::Success)
+            .exit_code(),
+        Opt::Culture {
+            command: Some(Command::Init { .. }),
+            ..
+        } => print_error(init_culture_cli(cli_options))
+            .map(|_| RuleOutcome::Success)
+            .exit_code(),
+        Opt::Culture {
+            command: Some(Command::List),
+            ..
+        } => print_error(list_rules(&mut stdout()))
+            .map(|_| RuleOutcome::Success)
             .exit_code(),
-    )
+        Opt::Culture {
+            command: Some(Command::Explain { ref rule }),
+            ..
+        } => print_error(explain_rule(&mut stdout(), rule))
+            .map(|_| RuleOutcome::Success)
+            .exit_code(),
+        _ => print_error(check_culture_cli(cli_options)).exit_code(),
+    };
+    std::process::exit(exit_code)
+}
+
+fn print_error<T>(result: Result<T, Error>) -> Result<T, Error> {
+    result.map_err(|e| {
+        eprintln!("{}", e);
+        e
+    })
 }
 
 /// Run `cargo_culture_kit::check_culture` with target project, verbosity,
 /// and selected rules based on command-line options. Prints to
-/// `std::io::stdout`.
-pub fn check_culture_cli(cli_options: Opt) -> Result<OutcomesByDescription, Error> {
+/// `std::io::stdout` in the requested `OutputFormat`.
+pub fn check_culture_cli(cli_options: Opt) -> Result<CultureReport, Error> {
+    let Opt::Culture {
+        manifest_path,
+        culture_checklist_file_path,
+        verbose,
+        jobs,
+        timeout,
+        rule_timeout,
+        no_cache,
+        format,
+        ..
+    } = cli_options;
+    let options = check_options(jobs, timeout, rule_timeout, no_cache);
+    with_requested_rules(culture_checklist_file_path, |rules| {
+        check_culture_formatted(&manifest_path, verbose, &options, format, rules)
+    })
+}
+
+/// Run `cargo_culture_kit::plan_scaffolds` with target project, verbosity,
+/// and selected rules based on command-line options, then create the
+/// planned files, or merely list them for a dry run.
+///
+/// Returns the planned `Scaffold`s.
+pub fn fix_culture_cli(cli_options: Opt) -> Result<Vec<Scaffold>, Error> {
     let Opt::Culture {
         manifest_path,
         culture_checklist_file_path,
         verbose,
+        command,
+        ..
+    } = cli_options;
+    let dry_run = match command {
+        Some(Command::Fix { dry_run }) => dry_run,
+        _ => false,
+    };
+    let scaffolds = with_requested_rules(culture_checklist_file_path, |rules| {
+        Ok(plan_scaffolds(
+            &manifest_path,
+            verbose,
+            &mut TextReporter::new(stdout()),
+            rules,
+        )?)
+    })?;
+    if scaffolds.is_empty() {
+        println!("No missing files to create.");
+    }
+    for scaffold in &scaffolds {
+        if dry_run {
+            println!("Would create {}", scaffold.path.display());
+        } else {
+            scaffold.write()?;
+            println!("Created {}", scaffold.path.display());
+        }
+    }
+    Ok(scaffolds)
+}
+
+/// Write a culture checklist file of the default rules to the root of the
+/// target project's workspace, optionally selecting only the rules that the
+/// project currently passes. The report of that check is printed to stdout
+/// in the requested format, and any other messages to stderr.
+///
+/// Returns the written checklist as a `Scaffold`.
+pub fn init_culture_cli(cli_options: Opt) -> Result<Scaffold, Error> {
+    let Opt::Culture {
+        manifest_path,
+        verbose,
+        jobs,
+        timeout,
+        rule_timeout,
+        no_cache,
+        format,
+        command,
+        ..
     } = cli_options;
-    match culture_checklist_file_path {
-        Some(ref f) if f.is_file() => check_culture_from_checklist(&manifest_path, verbose, f),
-        Some(f) => Err(FilterError::RuleChecklistReadError(format!(
-            "Could not find requested rules checklist file, {}",
-            f.display()
-        )).into()),
-        None => match find_extant_culture_file(&PathBuf::from(DEFAULT_CULTURE_CHECKLIST_FILE_NAME))
+    let only_passing = match command {
+        Some(Command::Init { only_passing }) => only_passing,
+        _ => false,
+    };
+    let checklist_path = ProjectInfo::read(&manifest_path)
+        .workspace_root
+        .join(DEFAULT_CULTURE_CHECKLIST_FILE_NAME);
+    if checklist_path.exists() {
+        return Err(ScaffoldError::FileAlreadyExists(checklist_path.display().to_string()).into());
+    }
+    let rules = default_rules();
+    let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+    let (selected, unselected) = if only_passing {
+        let options = check_options(jobs, timeout, rule_timeout, no_cache);
+        let report =
+            check_culture_formatted(&manifest_path, verbose, &options, format, &rules_refs)?;
+        rules_refs.iter().partition(|rule| {
+            report.get(rule.id()).map(|r| &r.outcome) == Some(&RuleOutcome::Success)
+        })
+    } else {
+        (rules_refs.clone(), Vec::new())
+    };
+    let checklist = Scaffold {
+        path: checklist_path,
+        contents: format_checklist(&selected, &unselected),
+    };
+    checklist.write()?;
+    // Kept off stdout, which may carry a machine-readable report
+    eprintln!("Created {}", checklist.path.display());
+    Ok(checklist)
+}
+
+/// Print every one of the default rules, and of the rules of any plugins
+/// found on the `PATH`, with its id, cost and description.
+pub fn list_rules<W: Write>(output: &mut W) -> Result<(), Error> {
+    let rules = available_rules(None)?;
+    let width = rules.iter().map(|r| r.id().len()).max().unwrap_or(0);
+    for rule in &rules {
+        writeln!(
+            output,
+            "{:width$}  {:9}  {}",
+            rule.id(),
+            rule.cost().to_string(),
+            rule.description(),
+            width = width
+        )?;
+    }
+    Ok(())
+}
+
+/// Print everything known about the one default or plugin rule best
+/// matching `request`, which may be the rule's id, its description, or a
+/// distinctive fragment of its description.
+pub fn explain_rule<W: Write>(output: &mut W, request: &str) -> Result<(), Error> {
+    let rules = available_rules(None)?;
+    let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+    let rule = find_rule(&rules_refs, request)?;
+    writeln!(output, "{}", rule.description())?;
+    writeln!(output)?;
+    writeln!(output, "Id: {}", rule.id())?;
+    writeln!(output, "Cost: {}", rule.cost())?;
+    if !rule.prerequisites().is_empty() {
+        writeln!(output, "Prerequisites: {}", rule.prerequisites().join(", "))?;
+    }
+    if let Some(justification) = rule.justification() {
+        writeln!(output)?;
+        writeln!(output, "Justification:")?;
+        writeln!(output, "    {}", justification)?;
+    }
+    if let Some(remediation) = rule.remediation() {
+        writeln!(output)?;
+        writeln!(output, "Remediation:")?;
+        writeln!(output, "    {}", remediation)?;
+    }
+    Ok(())
+}
+
+/// Supply `action` with the rules requested by the explicit
+/// `culture_checklist_file_path`, or else those of an implicit
+/// `".culture.toml"` or `".culture"` file, or else the default rules
+/// and the rules of any plugins found on the `PATH`.
+fn with_requested_rules<T, F>(
+    culture_checklist_file_path: Option<PathBuf>,
+    action: F,
+) -> Result<T, Error>
+where
+    F: FnOnce(&[&Rule]) -> Result<T, Error>,
+{
+    let checklist = match culture_checklist_file_path {
+        Some(ref f) if f.is_file() => Some(f.clone()),
+        Some(f) => {
+            return Err(FilterError::RuleChecklistReadError(format!(
+                "Could not find requested rules checklist file, {}",
+                f.display()
+            ))
+            .into())
+        }
+        None => find_extant_culture_config_file(&PathBuf::from(DEFAULT_CULTURE_CONFIG_FILE_NAME))
+            .or_else(|| {
+                find_extant_culture_file(&PathBuf::from(DEFAULT_CULTURE_CHECKLIST_FILE_NAME))
+            }),
+    };
+    let config = match checklist {
+        Some(ref f) if is_config_file(f) => Some(CultureConfig::from_file(f)?),
+        _ => None,
+    };
+    let rules = available_rules(config.as_ref())?;
+    let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+    match (config, checklist) {
+        (Some(ref c), _) => action(&c.enabled_rules(&rules_refs)?),
+        (None, Some(ref f)) => action(&filter_to_requested_rules_from_checklist_file(
+            f,
+            &rules_refs,
+        )?),
+        (None, None) => action(&rules_refs),
+    }
+}
+
+/// The rules of `config`, or else the default rules, along with the
+/// `ExternalRule`s of the plugins found on the `PATH` that `config` does
+/// not already list.
+///
+/// A plugin found on the `PATH` that cannot be loaded in time, or that
+/// reuses the id or description of another rule, is left out with a
+/// warning rather than failing the command.
+fn available_rules(config: Option<&CultureConfig>) -> Result<Vec<Box<Rule>>, Error> {
+    let default_config = CultureConfig::default();
+    let config = config.unwrap_or(&default_config);
+    let mut discovered: Vec<Box<Rule>> = Vec::new();
+    for plugin in find_plugins_on_path() {
+        if config
+            .plugins
+            .iter()
+            .any(|l| l.file_name() == plugin.file_name())
         {
-            None => Ok(check_culture_default(
-                manifest_path,
-                verbose,
-                &mut stdout(),
-            )?),
-            Some(ref f) => check_culture_from_checklist(&manifest_path, verbose, f),
-        },
+            continue;
+        }
+        match ExternalRule::load_with_timeout(plugin, PLUGIN_DESCRIBE_TIMEOUT) {
+            Ok(rule) => discovered.push(Box::new(rule)),
+            Err(e) => eprintln!("warning: skipping a rule plugin found on the PATH. {}", e),
+        }
+    }
+    let (rules, left_out) = config.configured_rules_with(discovered)?;
+    for id in left_out {
+        eprintln!(
+            "warning: skipping a rule plugin found on the PATH, \
+             as another rule already has the id {} or its description",
+            id
+        );
+    }
+    Ok(rules)
+}
+
+fn is_config_file(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "toml")
+}
+
+/// Assemble the `CheckOptions` requested on the command line, where
+/// timeouts are given in seconds
+fn check_options(
+    jobs: usize,
+    timeout: Option<u64>,
+    rule_timeout: Option<u64>,
+    no_cache: bool,
+) -> CheckOptions {
+    CheckOptions {
+        jobs,
+        timeout: timeout.map(Duration::from_secs),
+        rule_timeout: rule_timeout.map(Duration::from_secs),
+        cache: !no_cache,
     }
 }
 
-fn check_culture_from_checklist(
+fn check_culture_formatted(
     manifest_path: &Path,
     verbose: bool,
-    extant_rule_checklist_file: &Path,
-) -> Result<OutcomesByDescription, Error> {
-    assert!(extant_rule_checklist_file.is_file());
-    let rules = default_rules();
-    let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
-    let filtered_rules =
-        filter_to_requested_rules_from_checklist_file(extant_rule_checklist_file, &rules_refs)?;
-    Ok(check_culture(
+    options: &CheckOptions,
+    format: OutputFormat,
+    rules: &[&Rule],
+) -> Result<CultureReport, Error> {
+    let mut reporter: Box<Reporter> = match format {
+        OutputFormat::Text => Box::new(TextReporter::new(stdout())),
+        OutputFormat::Json => Box::new(JsonReporter::new(stdout())),
+        OutputFormat::Junit => Box::new(JunitReporter::new(stdout())),
+        OutputFormat::Sarif => Box::new(SarifReporter::new(stdout(), manifest_path)),
+        OutputFormat::Tap => Box::new(TapReporter::new(stdout())),
+        OutputFormat::Markdown => Box::new(MarkdownReporter::new(stdout())),
+        OutputFormat::Html => Box::new(HtmlReporter::new(stdout())),
+    };
+    Ok(check_culture_with_options(
         manifest_path,
         verbose,
-        &mut stdout(),
-        &filtered_rules,
+        options,
+        reporter.as_mut(),
+        rules,
     )?)
 }
 
@@ -140,7 +543,6 @@ mod tests {
     use std::env;
     use std::ffi::OsString;
     use std::fs::{create_dir_all, File};
-    use std::io::Write;
     use tempfile::tempdir;
 
     #[test]
@@ -155,16 +557,216 @@ mod tests {
         checklist_file
             .write_all(format!("{}", lone_rule_description).as_bytes())
             .expect("Could not write to checklist file");
-        let outcomes =
-            check_culture_from_checklist(&dir.path().join("Cargo.toml"), false, &checklist_path)
-                .expect("Should pass scrutiny");
+        let outcomes = check_culture_cli(Opt::Culture {
+            manifest_path: dir.path().join("Cargo.toml"),
+            culture_checklist_file_path: Some(checklist_path),
+            verbose: false,
+            jobs: 1,
+            timeout: None,
+            rule_timeout: None,
+            no_cache: false,
+            format: OutputFormat::Text,
+            command: None,
+        })
+        .expect("Should pass scrutiny");
         assert_eq!(1, outcomes.len());
         assert_eq!(
-            Some(&cargo_culture_kit::RuleOutcome::Success),
-            outcomes.get(lone_rule_description)
+            Some(&RuleOutcome::Success),
+            outcomes.get(lone_rule_description).map(|r| &r.outcome)
         );
     }
 
+    #[test]
+    fn check_culture_from_config_file() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path());
+        File::create(dir.path().join(".travis.yml"))
+            .and_then(|mut f| f.write_all(b"language: rust\n"))
+            .expect("Could not write CI file");
+        let config_path = dir.path().join(".culture.toml");
+        let config_options = |providers: &str| {
+            File::create(&config_path)
+                .and_then(|mut f| {
+                    write!(
+                        f,
+                        "rules = [\"has-continuous-integration-file\"]\n\n\
+                         [has-continuous-integration-file]\nproviders = [\"{}\"]\n",
+                        providers
+                    )
+                })
+                .expect("Could not write config file");
+            Opt::Culture {
+                manifest_path: dir.path().join("Cargo.toml"),
+                culture_checklist_file_path: Some(config_path.clone()),
+                verbose: false,
+                jobs: 1,
+                timeout: None,
+                rule_timeout: None,
+                no_cache: false,
+                format: OutputFormat::Text,
+                command: None,
+            }
+        };
+        let travis = check_culture_cli(config_options("travis")).expect("Should check");
+        assert_eq!(1, travis.len());
+        assert_eq!(
+            Some(&RuleOutcome::Success),
+            travis
+                .get("has-continuous-integration-file")
+                .map(|r| &r.outcome)
+        );
+        let gitlab = check_culture_cli(config_options("gitlab-ci")).expect("Should check");
+        assert_eq!(
+            Some(&RuleOutcome::Failure),
+            gitlab
+                .get("has-continuous-integration-file")
+                .map(|r| &r.outcome)
+        );
+        assert!(check_culture_cli(config_options("jenkins")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_culture_with_config_file_plugin() {
+        use std::fs::set_permissions;
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path());
+        let plugin_path = dir.path().join("cargo-culture-rule-always");
+        File::create(&plugin_path)
+            .and_then(|mut f| {
+                f.write_all(
+                    br#"#!/bin/sh
+case "$(cat)" in
+  *describe*) echo '{"id": "always", "description": "Should always pass."}' ;;
+  *) echo '{"outcome": "success"}' ;;
+esac
+"#,
+                )
+            })
+            .expect("Could not write plugin");
+        set_permissions(&plugin_path, PermissionsExt::from_mode(0o755))
+            .expect("Could not make plugin executable");
+        let config_path = dir.path().join(".culture.toml");
+        File::create(&config_path)
+            .and_then(|mut f| {
+                f.write_all(b"rules = [\"always\"]\nplugins = [\"cargo-culture-rule-always\"]\n")
+            })
+            .expect("Could not write config file");
+        let report = check_culture_cli(Opt::Culture {
+            manifest_path: dir.path().join("Cargo.toml"),
+            culture_checklist_file_path: Some(config_path),
+            verbose: false,
+            jobs: 1,
+            timeout: None,
+            rule_timeout: None,
+            no_cache: false,
+            format: OutputFormat::Text,
+            command: None,
+        })
+        .expect("Should check");
+        assert_eq!(1, report.len());
+        assert_eq!(
+            Some(&RuleOutcome::Success),
+            report.get("always").map(|r| &r.outcome)
+        );
+    }
+
+    #[test]
+    fn fix_dry_run_lists_without_writing() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path());
+        let checklist_path = dir.path().join(".culture");
+        File::create(&checklist_path)
+            .and_then(|mut f| {
+                f.write_all(
+                    cargo_culture_kit::HasRustfmtFile::default()
+                        .description()
+                        .as_bytes(),
+                )
+            })
+            .expect("Could not write to checklist file");
+        let fix_options = |dry_run| Opt::Culture {
+            manifest_path: dir.path().join("Cargo.toml"),
+            culture_checklist_file_path: Some(checklist_path.clone()),
+            verbose: false,
+            jobs: 1,
+            timeout: None,
+            rule_timeout: None,
+            no_cache: false,
+            format: OutputFormat::Text,
+            command: Some(Command::Fix { dry_run }),
+        };
+        let rustfmt_path = dir.path().join("rustfmt.toml");
+        let planned = fix_culture_cli(fix_options(true)).expect("Should plan");
+        assert_eq!(
+            vec![rustfmt_path.clone()],
+            planned.into_iter().map(|s| s.path).collect::<Vec<_>>()
+        );
+        assert!(!rustfmt_path.exists());
+
+        fix_culture_cli(fix_options(false)).expect("Should fix");
+        assert!(rustfmt_path.is_file());
+        let replanned = fix_culture_cli(fix_options(false)).expect("Should plan again");
+        assert!(replanned.is_empty());
+    }
+
+    #[test]
+    fn init_writes_checklist_of_default_rules_once() {
+        let dir = tempdir().expect("Failed to make a temp dir");
+        write_package_cargo_toml(dir.path());
+        let init_options = || Opt::Culture {
+            manifest_path: dir.path().join("Cargo.toml"),
+            culture_checklist_file_path: None,
+            verbose: false,
+            jobs: 1,
+            timeout: None,
+            rule_timeout: None,
+            no_cache: false,
+            format: OutputFormat::Text,
+            command: Some(Command::Init {
+                only_passing: false,
+            }),
+        };
+        let checklist = init_culture_cli(init_options()).expect("Should write a checklist");
+        assert_eq!(dir.path().join(".culture"), checklist.path);
+        let rules = default_rules();
+        let rules_refs = rules.iter().map(|r| r.as_ref()).collect::<Vec<&Rule>>();
+        let requested = filter_to_requested_rules_from_checklist_file(&checklist.path, &rules_refs)
+            .expect("Should read back the checklist");
+        assert_eq!(rules_refs.len(), requested.len());
+        assert!(init_culture_cli(init_options()).is_err());
+    }
+
+    #[test]
+    fn list_shows_every_default_rule() {
+        let mut output: Vec<u8> = Vec::new();
+        list_rules(&mut output).expect("Should list rules");
+        let listing = String::from_utf8(output).expect("Output should be utf8");
+        for rule in default_rules() {
+            let line = listing
+                .lines()
+                .find(|l| l.ends_with(rule.description()))
+                .expect("Should list every rule");
+            assert!(line.starts_with(rule.id()));
+            assert!(line.contains(&rule.cost().to_string()));
+        }
+    }
+
+    #[test]
+    fn explain_finds_rules_by_identifier_or_description_fragment() {
+        let rule = cargo_culture_kit::HasRustfmtFile::default();
+        for request in &["has-rustfmt-file", "Has-Rustfmt-File", "RUSTFMT"] {
+            let mut output: Vec<u8> = Vec::new();
+            explain_rule(&mut output, request).expect("Should explain the rule");
+            let explanation = String::from_utf8(output).expect("Output should be utf8");
+            assert!(explanation.starts_with(rule.description()));
+            assert!(explanation.contains(rule.justification().expect("Should be justified")));
+            assert!(explanation.contains(rule.remediation().expect("Should be remediable")));
+        }
+        assert!(explain_rule(&mut Vec::new(), "Should have a").is_err());
+    }
+
     fn write_package_cargo_toml(project_dir: &Path) {
         let cargo_path = project_dir.join("Cargo.toml");
         let mut cargo_file = File::create(cargo_path).expect("Could not make target file");
@@ -203,7 +805,8 @@ mod tests {
     }
 }
         "##,
-        ).expect("Could not write to target file");
+        )
+        .expect("Could not write to target file");
     }
 
     fn arb_basedir() -> BoxedStrategy<PathBuf> {
@@ -218,7 +821,8 @@ mod tests {
             Just(PathBuf::from("..")),
             Just(PathBuf::from("../../..")),
             Just(PathBuf::new()),
-        ].boxed()
+        ]
+        .boxed()
     }
 
     prop_compose! {
@@ -227,6 +831,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn output_format_parses_known_names() {
+        for name in OutputFormat::NAMES {
+            assert!(OutputFormat::from_str(name).is_ok());
+        }
+        assert_eq!(Ok(OutputFormat::Json), OutputFormat::from_str("json"));
+        assert!(OutputFormat::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn opt_parses_format() {
+        let result = Opt::from_iter_safe(vec!["cargo", "culture", "--format", "json"]);
+        match result {
+            Ok(Opt::Culture { format, .. }) => assert_eq!(OutputFormat::Json, format),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn opt_parses_jobs() {
+        let result = Opt::from_iter_safe(vec!["cargo", "culture", "-j", "4"]);
+        match result {
+            Ok(Opt::Culture { jobs, .. }) => assert_eq!(4, jobs),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn opt_parses_no_cache() {
+        let result = Opt::from_iter_safe(vec!["cargo", "culture", "--no-cache"]);
+        match result {
+            Ok(Opt::Culture { no_cache, .. }) => assert!(no_cache),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn opt_parses_timeouts() {
+        let result = Opt::from_iter_safe(vec![
+            "cargo",
+            "culture",
+            "--timeout",
+            "600",
+            "--rule-timeout",
+            "120",
+        ]);
+        match result {
+            Ok(Opt::Culture {
+                jobs,
+                timeout,
+                rule_timeout,
+                no_cache,
+                ..
+            }) => assert_eq!(
+                CheckOptions {
+                    jobs: 1,
+                    timeout: Some(Duration::from_secs(600)),
+                    rule_timeout: Some(Duration::from_secs(120)),
+                    cache: true,
+                },
+                check_options(jobs, timeout, rule_timeout, no_cache)
+            ),
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    #[test]
+    fn opt_parses_fix_subcommand() {
+        let result = Opt::from_iter_safe(vec![
+            "cargo",
+            "culture",
+            "--manifest-path",
+            "kid/Cargo.toml",
+            "fix",
+            "--dry-run",
+        ]);
+        match result {
+            Ok(Opt::Culture {
+                manifest_path,
+                command,
+                ..
+            }) => {
+                assert_eq!(PathBuf::from("kid/Cargo.toml"), manifest_path);
+                assert_eq!(Some(Command::Fix { dry_run: true }), command);
+            }
+            Err(e) => panic!("{}", e),
+        }
+    }
+
     proptest! {
         #[test]
         fn opt_parseable_from_arbitrary_inputs(
@@ -248,7 +941,13 @@ mod tests {
                         Opt::Culture {
                             manifest_path: path.clone(),
                             culture_checklist_file_path: None,
-                            verbose: *verbose},
+                            verbose: *verbose,
+                            jobs: 1,
+                            timeout: None,
+                            rule_timeout: None,
+                            no_cache: false,
+                            format: OutputFormat::Text,
+                            command: None},
                         o)
                 },
                 Err(e) => panic!("{}", e),